@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::Points;
+use crate::game_options::GameOptions;
+use crate::player::PlayerId;
+
+/// A single entry in the auction. points of None is a pass.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Bid {
+    pub player: PlayerId,
+    pub points: Option<Points>,
+}
+
+/// A Spades bid: the tricks a player means to take. Nil is a bid to take
+/// none, and blind nil is bid before looking at the hand.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TrickBid {
+    Tricks(u8),
+    Nil,
+    BlindNil,
+}
+
+impl TrickBid {
+    /// The tricks counted toward the partnership's contract.
+    pub fn tricks(&self) -> u8 {
+        match self {
+            TrickBid::Tricks(tricks) => *tricks,
+            TrickBid::Nil | TrickBid::BlindNil => 0,
+        }
+    }
+
+    pub fn is_nil(&self) -> bool {
+        matches!(self, TrickBid::Nil | TrickBid::BlindNil)
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            TrickBid::Tricks(tricks) => tricks.to_string(),
+            TrickBid::Nil => "Nil".to_string(),
+            TrickBid::BlindNil => "Blind nil".to_string(),
+        }
+    }
+}
+
+/// The auction for one hand. Players bid points in turn. A player who passes
+/// is out of the auction for the rest of the hand.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Auction {
+    /// Every bid and pass, in the order made.
+    pub history: Vec<Bid>,
+    pub passed: Vec<bool>,
+    pub high_bid: Option<Bid>,
+    /// The opening bid must be at least this much. Usually
+    /// GameOptions::bid_minimum, but lower if bidding was reopened.
+    pub minimum: Points,
+    /// Everyone passed once already and bidding reopened. See AllPassPolicy::ReopenLower.
+    pub reopened: bool,
+}
+
+impl Auction {
+    pub fn new(player_count: PlayerId, minimum: Points) -> Self {
+        Self {
+            history: Vec::new(),
+            passed: vec![false; player_count],
+            high_bid: None,
+            minimum,
+            reopened: false,
+        }
+    }
+
+    pub fn pass_count(&self) -> usize {
+        self.passed.iter().filter(|p| **p).count()
+    }
+
+    pub fn in_auction(&self, p: PlayerId) -> bool {
+        !self.passed[p]
+    }
+
+    /// The lowest bid that may be made next.
+    pub fn next_minimum(&self, options: &GameOptions) -> Points {
+        match self.high_bid {
+            Some(bid) => bid.points.unwrap() + options.bid_increment,
+            None => self.minimum,
+        }
+    }
+
+    pub fn is_legal(&self, points: Points, options: &GameOptions) -> bool {
+        points >= self.next_minimum(options)
+            && points <= options.bid_maximum
+            && (points - self.minimum) % options.bid_increment == 0
+    }
+
+    /// The legal bids, lowest first.
+    pub fn legal_bids(&self, options: &GameOptions) -> Vec<Points> {
+        let mut bids = Vec::new();
+        let mut points = self.next_minimum(options);
+        while points <= options.bid_maximum {
+            bids.push(points);
+            points += options.bid_increment;
+        }
+        bids
+    }
+
+    pub fn record(&mut self, bid: Bid) {
+        match bid.points {
+            Some(_) => self.high_bid = Some(bid),
+            None => self.passed[bid.player] = true,
+        }
+        self.history.push(bid);
+    }
+
+    /// The auction is over when everyone has passed, when only the high bidder
+    /// remains, or when the maximum has been bid.
+    pub fn closed(&self, options: &GameOptions) -> bool {
+        let players_left = self.passed.len() - self.pass_count();
+        match self.high_bid {
+            Some(bid) => players_left <= 1 || bid.points == Some(options.bid_maximum),
+            None => players_left == 0,
+        }
+    }
+}
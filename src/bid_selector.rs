@@ -6,21 +6,46 @@ use notan::math::Affine2;
 use notan::math::Vec2;
 use notan::prelude::*;
 
-use crate::card::CardSuit;
+use crate::bid::TrickBid;
+use crate::card::{CardId, CardSuit, Points};
 use crate::game::PlayerAction;
 use crate::image_button::ImageButton;
 use crate::transform::Transform;
 use crate::view_geom::ViewGeom;
 use crate::view_trait::ViewTrait;
 
+/// What the selector is asking the player for.
+#[derive(Clone, Copy, PartialEq)]
+pub enum BidMode {
+    Points,
+    Tricks,
+    /// Bid blind nil or look at the cards first.
+    BlindNil,
+    /// Order up or name trump, or pass, as in Euchre.
+    TrumpBid,
+    Trump,
+    Call,
+    Alone,
+}
+
 pub struct BidSelector {
     pub visible: bool,
     pub transform: Transform,
     pub z_order: usize,
     pub texture: Texture,
+    pub mode: BidMode,
 
     pub pass_button: ImageButton<PlayerAction>,
     suit_buttons: Vec<ImageButton<PlayerAction>>,
+    no_trump_button: ImageButton<PlayerAction>,
+
+    // Points, Tricks, TrumpBid and Call modes. The lower and raise buttons step through the
+    // choices and the choose button sends the one showing.
+    lower_button: ImageButton<PlayerAction>,
+    choose_button: ImageButton<PlayerAction>,
+    raise_button: ImageButton<PlayerAction>,
+    choices: Vec<(String, PlayerAction)>,
+    choice_idx: usize,
 }
 
 impl BidSelector {
@@ -40,17 +65,156 @@ impl BidSelector {
 
         let pass_button = BidSelector::create_pass_button(sender.clone());
         let suit_buttons = BidSelector::create_suit_buttons(suits, sender.clone());
+        let mut no_trump_button =
+            BidSelector::create_square_button(vec2(240., 55.), "NT", Some(sender.clone()));
+        no_trump_button.mouse_up_message = Some(PlayerAction::ChooseTrump(None));
+        let lower_button = BidSelector::create_square_button(vec2(25., 55.), "-", None);
+        let raise_button = BidSelector::create_square_button(vec2(125., 55.), "+", None);
+        let choose_button = BidSelector::create_square_button(vec2(180., 55.), "", Some(sender));
 
         Self {
             visible: false,
             transform: trans,
             z_order: 0,
             texture,
+            mode: BidMode::Points,
             pass_button,
             suit_buttons,
+            no_trump_button,
+            lower_button,
+            choose_button,
+            raise_button,
+            choices: Vec::new(),
+            choice_idx: 0,
         }
     }
 
+    /// Show the selector with the given legal bids, lowest first.
+    pub fn show_bids(&mut self, bids: Vec<Points>) {
+        self.mode = BidMode::Points;
+        self.choose_button.text = "Bid".to_string();
+        self.choices = bids
+            .iter()
+            .map(|points| (points.to_string(), PlayerAction::MakeBid(Some(*points))))
+            .collect();
+        self.set_choice_idx(0);
+        self.visible = true;
+    }
+
+    /// Show the selector with the legal trick bids, nil first.
+    pub fn show_trick_bids(&mut self, bids: &[TrickBid]) {
+        self.mode = BidMode::Tricks;
+        self.choose_button.text = "Bid".to_string();
+        self.choices = bids
+            .iter()
+            .map(|bid| (bid.name(), PlayerAction::BidTricks(*bid)))
+            .collect();
+        self.set_choice_idx(0);
+        self.visible = true;
+    }
+
+    /// Ask a player who hasn't looked at their cards whether to bid blind nil.
+    pub fn show_blind_nil(&mut self) {
+        self.mode = BidMode::BlindNil;
+        self.choose_button.text = "OK".to_string();
+        self.choices = vec![
+            ("Look".to_string(), PlayerAction::LookAtHand),
+            (
+                TrickBid::BlindNil.name(),
+                PlayerAction::BidTricks(TrickBid::BlindNil),
+            ),
+        ];
+        self.set_choice_idx(0);
+        self.visible = true;
+    }
+
+    /// Show the selector with the legal trump bids, pass first.
+    pub fn show_trump_bids(&mut self, bids: &[Option<CardSuit>]) {
+        self.mode = BidMode::TrumpBid;
+        self.choose_button.text = "OK".to_string();
+        self.choices = bids
+            .iter()
+            .map(|suit| {
+                let text = match suit {
+                    Some(suit) => suit.to_string(),
+                    None => "Pass".to_string(),
+                };
+                (text, PlayerAction::BidTrump(*suit))
+            })
+            .collect();
+        self.set_choice_idx(0);
+        self.visible = true;
+    }
+
+    /// Show the selector with the cards the maker may call, named for display.
+    pub fn show_call(&mut self, cards: Vec<(String, CardId)>) {
+        self.mode = BidMode::Call;
+        self.choose_button.text = "Call".to_string();
+        self.choices = cards
+            .into_iter()
+            .map(|(name, id)| (name, PlayerAction::CallPartner(id)))
+            .collect();
+        self.set_choice_idx(0);
+        self.visible = true;
+    }
+
+    /// Ask the maker whether to go alone.
+    pub fn show_alone(&mut self) {
+        self.mode = BidMode::Alone;
+        self.choose_button.text = "Go".to_string();
+        self.choices = vec![
+            ("Partner".to_string(), PlayerAction::GoAlone(false)),
+            ("Alone".to_string(), PlayerAction::GoAlone(true)),
+        ];
+        self.set_choice_idx(0);
+        self.visible = true;
+    }
+
+    /// Show the selector with the buttons for the suits that may be named,
+    /// plus no trump if allowed.
+    pub fn show_trump(&mut self, suits: &[CardSuit], allow_no_trump: bool) {
+        self.mode = BidMode::Trump;
+        for button in &mut self.suit_buttons {
+            button.visible = match button.mouse_up_message {
+                Some(PlayerAction::ChooseTrump(Some(suit))) => suits.contains(&suit),
+                _ => false,
+            };
+        }
+        self.no_trump_button.visible = allow_no_trump;
+        self.visible = true;
+    }
+
+    fn set_choice_idx(&mut self, idx: usize) {
+        match self.choices.get(idx) {
+            Some((_, action)) => {
+                self.choice_idx = idx;
+                self.choose_button.mouse_up_message = Some(*action);
+                self.choose_button.visible = true;
+            }
+            None => {
+                // Nothing left to bid. Passing is the only choice.
+                self.choose_button.visible = false;
+            }
+        }
+    }
+
+    fn choice_text(&self) -> &str {
+        match self.choices.get(self.choice_idx) {
+            Some((text, _)) => text,
+            None => "",
+        }
+    }
+
+    fn create_square_button(
+        trans: Vec2,
+        text: &str,
+        sender: Option<Sender<PlayerAction>>,
+    ) -> ImageButton<PlayerAction> {
+        let mut button = ImageButton::new(trans, "square", "square", "", 0.4, text, sender);
+        button.font_size = 16.0;
+        button
+    }
+
     fn create_pass_button(sender: Sender<PlayerAction>) -> ImageButton<PlayerAction> {
         let trans = vec2(266., 55.);
         let mut button = ImageButton::new(
@@ -78,7 +242,7 @@ impl BidSelector {
                 CardSuit::Diamond => ("diamond", "diamond_mouse_over"),
                 CardSuit::Heart => ("heart", "heart_mouse_over"),
                 CardSuit::Spade => ("spade", "spade_mouse_over"),
-                CardSuit::Joker => panic!(),
+                CardSuit::Joker | CardSuit::Bird => panic!(),
             };
 
             let trans = vec2(32. + 50. * idx as f32, 53.);
@@ -91,7 +255,7 @@ impl BidSelector {
                 "",
                 Some(sender.clone()),
             );
-            button.mouse_up_message = Some(PlayerAction::MakeBid(Some(*suit)));
+            button.mouse_up_message = Some(PlayerAction::ChooseTrump(Some(*suit)));
             buttons.push(button);
         }
         buttons
@@ -114,16 +278,55 @@ impl ViewTrait for BidSelector {
 
         let affine = *parent_affine * self.transform.affine2();
 
-        for button in &mut self.suit_buttons {
-            if button.handle_mouse_event(event, screen_pt, &affine, send_msg) {
-                send_msg = false;
+        if self.mode == BidMode::Trump {
+            for button in &mut self.suit_buttons {
+                if button.handle_mouse_event(event, screen_pt, &affine, send_msg) {
+                    send_msg = false;
+                    contains = true;
+                }
+            }
+            if self
+                .no_trump_button
+                .handle_mouse_event(event, screen_pt, &affine, send_msg)
+            {
                 contains = true;
             }
+            return contains;
         }
 
+        let mouse_up = matches!(event, Event::MouseUp { .. });
+        if self
+            .lower_button
+            .handle_mouse_event(event, screen_pt, &affine, send_msg)
+        {
+            if mouse_up && self.choice_idx > 0 {
+                self.set_choice_idx(self.choice_idx - 1);
+            }
+            send_msg = false;
+            contains = true;
+        }
+        if self
+            .raise_button
+            .handle_mouse_event(event, screen_pt, &affine, send_msg)
+        {
+            if mouse_up && self.choice_idx + 1 < self.choices.len() {
+                self.set_choice_idx(self.choice_idx + 1);
+            }
+            send_msg = false;
+            contains = true;
+        }
         if self
-            .pass_button
+            .choose_button
             .handle_mouse_event(event, screen_pt, &affine, send_msg)
+        {
+            send_msg = false;
+            contains = true;
+        }
+
+        if self.mode == BidMode::Points
+            && self
+                .pass_button
+                .handle_mouse_event(event, screen_pt, &affine, send_msg)
         {
             contains = true;
         }
@@ -137,14 +340,65 @@ impl ViewTrait for BidSelector {
         }
 
         let (size_x, size_y) = self.transform.size().into();
-        draw.image(&self.texture)
-            .transform(self.transform.mat3_with_parent(parent_affine))
-            .size(size_x, size_y);
-
         let affine = *parent_affine * self.transform.affine2();
-        for button in &mut self.suit_buttons {
-            button.draw(draw, &affine);
+
+        match self.mode {
+            BidMode::Trump => {
+                draw.image(&self.texture)
+                    .transform(self.transform.mat3_with_parent(parent_affine))
+                    .size(size_x, size_y);
+
+                for button in &mut self.suit_buttons {
+                    button.draw(draw, &affine);
+                }
+                self.no_trump_button.draw(draw, &affine);
+            }
+            BidMode::Points
+            | BidMode::Tricks
+            | BidMode::BlindNil
+            | BidMode::TrumpBid
+            | BidMode::Call
+            | BidMode::Alone => {
+                // The texture's caption is for choosing trump, so draw a plain panel.
+                draw.rect((0.0, 0.0), (size_x, size_y))
+                    .transform(self.transform.mat3_with_parent(parent_affine))
+                    .corner_radius(6.0)
+                    .fill()
+                    .fill_color(crate::view::LIGHT_GRAY)
+                    .stroke(1.0)
+                    .stroke_color(Color::BLACK);
+
+                let caption = match self.mode {
+                    BidMode::Call => "Call a Partner Card",
+                    BidMode::Alone => "Go Alone?",
+                    BidMode::Tricks => "Bid Tricks",
+                    BidMode::BlindNil => "Blind Nil?",
+                    BidMode::TrumpBid => "Bid Trump",
+                    _ => "Bid or Pass",
+                };
+                let font = crate::FONT.lock().unwrap().expect("Font is None");
+                draw.text(&font, caption)
+                    .position(size_x * 0.5, 14.0)
+                    .transform(self.transform.mat3_with_parent(parent_affine))
+                    .size(16.0 * 2.0)
+                    .h_align_center()
+                    .v_align_middle()
+                    .color(Color::BLACK);
+                draw.text(&font, self.choice_text())
+                    .position(75.0, 55.0)
+                    .transform(self.transform.mat3_with_parent(parent_affine))
+                    .size(14.0 * 2.0)
+                    .h_align_center()
+                    .v_align_middle()
+                    .color(Color::BLACK);
+
+                self.lower_button.draw(draw, &affine);
+                self.choose_button.draw(draw, &affine);
+                self.raise_button.draw(draw, &affine);
+                if self.mode == BidMode::Points {
+                    self.pass_button.draw(draw, &affine);
+                }
+            }
         }
-        self.pass_button.draw(draw, &affine);
     }
 }
@@ -2,53 +2,121 @@ use std::sync::mpsc::Sender;
 use std::thread;
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
+
+use crate::bid::TrickBid;
 use crate::bot_monte::BotMonte;
 use crate::bot_random::BotRandom;
 
-use crate::card::{Card, CardId, CardSuit, GameRank};
+use crate::card::{Card, CardId, CardSuit, GameRank, Points};
 use crate::game::{Game, PlayerAction};
+use crate::player::PlayerId;
+use crate::trick::Trick;
 
 #[allow(unused_variables)]
 pub trait Bot {
-    fn make_bid(&self, game: &Game) -> Option<CardSuit>;
-    //fn choose_trump(&self, game: &Game) -> CardSuit;
-    fn play_card(&self, game: &Game) -> CardId;
+    fn make_bid(&mut self, game: &Game) -> Option<Points>;
+    /// One of Game::legal_trick_bids.
+    fn bid_tricks(&mut self, game: &Game) -> TrickBid;
+    /// One of Game::legal_trump_bids. None passes.
+    fn bid_trump(&mut self, game: &Game) -> Option<CardSuit>;
+    /// None for no trump, if the options allow it.
+    fn choose_trump(&mut self, game: &Game) -> Option<CardSuit>;
+    fn discard(&mut self, game: &Game) -> CardId;
+    /// A card to pass in Hearts.
+    fn pass_card(&mut self, game: &Game) -> CardId;
+    fn call_partner(&mut self, game: &Game) -> CardId;
+    fn go_alone(&mut self, game: &Game) -> bool;
+    /// A player to accuse of reneging, from what the bot has seen.
+    fn call_renege(&mut self, game: &Game) -> Option<PlayerId>;
+    fn play_card(&mut self, game: &Game) -> CardId;
 }
 
 pub struct BotMgr {}
 
 impl BotMgr {
-    fn get_bot(bot_kind: BotKind) -> Box<dyn Bot> {
+    /// The active player's bot, with an Rng seeded from the game so its
+    /// choices replay with the game's seed.
+    fn get_bot(game: &Game) -> Box<dyn Bot> {
         thread::sleep(Duration::from_millis(10));
-        match bot_kind {
-            BotKind::Random => Box::new(BotRandom::new()),
+        let rng = fastrand::Rng::with_seed(game.bot_seed);
+        match game.active_player().bot_kind.unwrap() {
+            BotKind::Random => Box::new(BotRandom::new(rng)),
             //BotKind::Rule => Box::new(BotRule::new()),
-            BotKind::Monte => Box::new(BotMonte::new()),
+            BotKind::Monte => Box::new(BotMonte::new(rng)),
         }
     }
 
     pub fn make_bid(game: &Game, sender: Sender<PlayerAction>) {
-        let player = &game.active_player();
-        let bot = BotMgr::get_bot(player.bot_kind.unwrap());
+        let mut bot = BotMgr::get_bot(game);
         let bid = bot.make_bid(game);
         sender
             .send(PlayerAction::MakeBid(bid))
             .expect("BotMessage send error.");
     }
 
-    // pub fn choose_trump(game: &Game, sender: Sender<PlayerAction>) {
-    //     let player = &game.active_player();
-    //     let bot = BotMgr::get_bot(player.bot_kind.unwrap());
-    //     let suit = bot.choose_trump(game);
-    //     sender
-    //         .send(PlayerAction::MakeBid(Some(suit)))
-    //         .expect("BotMessage send error.");
-    // }
+    pub fn bid_tricks(game: &Game, sender: Sender<PlayerAction>) {
+        let mut bot = BotMgr::get_bot(game);
+        let bid = bot.bid_tricks(game);
+        sender
+            .send(PlayerAction::BidTricks(bid))
+            .expect("BotMessage send error.");
+    }
+
+    pub fn bid_trump(game: &Game, sender: Sender<PlayerAction>) {
+        let mut bot = BotMgr::get_bot(game);
+        let suit = bot.bid_trump(game);
+        sender
+            .send(PlayerAction::BidTrump(suit))
+            .expect("BotMessage send error.");
+    }
+
+    pub fn choose_trump(game: &Game, sender: Sender<PlayerAction>) {
+        let mut bot = BotMgr::get_bot(game);
+        let suit = bot.choose_trump(game);
+        sender
+            .send(PlayerAction::ChooseTrump(suit))
+            .expect("BotMessage send error.");
+    }
+
+    pub fn call_partner(game: &Game, sender: Sender<PlayerAction>) {
+        let mut bot = BotMgr::get_bot(game);
+        let id = bot.call_partner(game);
+        sender
+            .send(PlayerAction::CallPartner(id))
+            .expect("BotMessage send error.");
+    }
+
+    pub fn go_alone(game: &Game, sender: Sender<PlayerAction>) {
+        let mut bot = BotMgr::get_bot(game);
+        let alone = bot.go_alone(game);
+        sender
+            .send(PlayerAction::GoAlone(alone))
+            .expect("BotMessage send error.");
+    }
+
+    /// Discards one card at a time, then ends the exchange once the nest is full.
+    /// A Hearts pass goes through the exchange the same way.
+    pub fn discard(game: &Game, sender: Sender<PlayerAction>) {
+        let mut bot = BotMgr::get_bot(game);
+        let action = match (game.nest_exchange_ready(), game.passing) {
+            (true, _) => PlayerAction::EndNestExchange,
+            (false, true) => PlayerAction::MoveCardToNest(bot.pass_card(game)),
+            (false, false) => PlayerAction::MoveCardToNest(bot.discard(game)),
+        };
+        sender.send(action).expect("BotMessage send error.");
+    }
 
     pub fn play_card(game: &Game, sender: Sender<PlayerAction>) {
-        let player = &game.active_player();
-        let bot = BotMgr::get_bot(player.bot_kind.unwrap());
+        let mut bot = BotMgr::get_bot(game);
         let p_id = game.active_player;
+        if game.options.renege.allowed {
+            if let Some(accused) = bot.call_renege(game) {
+                sender
+                    .send(PlayerAction::CallRenege(p_id, accused))
+                    .expect("BotMessage send error.");
+            }
+        }
         let c_id = bot.play_card(game);
         sender
             .send(PlayerAction::PlayCard(p_id, c_id))
@@ -88,6 +156,55 @@ impl BotMgr {
         suit_ids
     }
 
+    pub fn ids_without_points(ids: &[CardId], game: &Game) -> Vec<CardId> {
+        ids.iter()
+            .filter(|id| game.cards[**id].points == 0)
+            .copied()
+            .collect()
+    }
+
+    /// Players the tricks of this hand show reneging: they failed to follow a
+    /// lead, then later played a card that would have followed it.
+    pub fn provable_reneges(game: &Game) -> Vec<PlayerId> {
+        let mut tricks: Vec<&Trick> = game.players.iter().flat_map(|p| &p.tricks).collect();
+        tricks.push(&game.trick);
+        tricks.sort_by_key(|trick| trick.number);
+
+        let mut reneges = Vec::new();
+        for p in 0..game.player_count {
+            let mut failed_leads: Vec<&Card> = Vec::new();
+            for trick in &tricks {
+                if let (Some(id), Some(lead_card)) = (trick.card_ids[p], &trick.lead_card) {
+                    let card = &game.cards[id];
+                    if failed_leads.iter().any(|lead| card.follows(lead)) {
+                        reneges.push(p);
+                        break;
+                    }
+                    if !card.follows(lead_card) {
+                        failed_leads.push(lead_card);
+                    }
+                }
+            }
+        }
+        reneges
+    }
+
+    /// An opponent not yet caught whose renege the active player can prove,
+    /// noticed one time in chance_in.
+    pub fn notice_renege(game: &Game, rng: &mut fastrand::Rng, chance_in: u8) -> Option<PlayerId> {
+        let me = game.active_player;
+        BotMgr::provable_reneges(game)
+            .into_iter()
+            .filter(|p| *p != me && !game.players[me].partners.contains(p))
+            .filter(|p| {
+                !game
+                    .renege_calls
+                    .iter()
+                    .any(|call| call.correct && call.accused == *p)
+            })
+            .find(|_| rng.u8(0..chance_in) == 0)
+    }
+
     // #[allow(dead_code)]
     // pub fn ids_that_take_trick_lead(ids: &[CardId], game: &Game) -> Vec<CardId> {
     //     let mut trick_leaders = Vec::new();
@@ -129,7 +246,7 @@ impl BotMgr {
 }
 
 #[allow(dead_code)]
-#[derive(Copy, Clone, PartialEq)]
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BotKind {
     Random,
     //Rule,
@@ -1,21 +1,56 @@
-//use crate::bid::Bid;
+use crate::bid::TrickBid;
 use crate::bot::Bot;
 use crate::bot_random::BotRandom;
-use crate::card::{Card, CardId, CardSuit};
+use crate::bot::BotMgr;
+use crate::card::{Card, CardId, CardSuit, Points};
 use crate::game::Game;
-use crate::player::PlayerKind;
+use crate::player::PlayerId;
 
 /// A MonteCarlo bot. Only card play is MonteCarlo'd at this point.
 /// Bidding and discarding are stil rule based.
 #[derive(Clone)]
-pub struct BotMonte {}
+pub struct BotMonte {
+    rng: fastrand::Rng,
+}
 
 impl BotMonte {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(rng: fastrand::Rng) -> Self {
+        Self { rng }
+    }
+
+    fn strongest_suit(&self, hand: &Vec<Card>) -> CardSuit {
+        let suits = [
+            CardSuit::Club,
+            CardSuit::Diamond,
+            CardSuit::Heart,
+            CardSuit::Spade,
+        ];
+        self.strongest_of(hand, &suits)
+    }
+
+    fn strongest_of(&self, hand: &Vec<Card>, suits: &[CardSuit]) -> CardSuit {
+        let mut best_suit = suits[0];
+        let mut best_strength = 0.0;
+        for suit in suits.iter().copied() {
+            let strength = self.suit_strength(hand, &suit);
+            if strength > best_strength {
+                best_strength = strength;
+                best_suit = suit;
+            }
+        }
+        best_suit
+    }
+
+    /// What the finished hand is worth to player p. Penalty points count
+    /// against them when the low score wins.
+    fn hand_value(&self, game: &mut Game, p: PlayerId) -> Points {
+        let score = game.rules().score_hand(game)[p].total();
+        match game.rules().low_score_wins() {
+            true => -score,
+            false => score,
+        }
     }
 
-    #[allow(dead_code)]
     fn suit_strength(&self, hand: &Vec<Card>, suit: &CardSuit) -> f32 {
         let mut points = 0.0;
         for card in hand {
@@ -29,28 +64,185 @@ impl BotMonte {
 }
 
 impl Bot for BotMonte {
-    // Pass
-    fn make_bid(&self, _game: &Game) -> Option<CardSuit> {
-        None
+    // Estimate what the full hand is worth from the cards dealt so far: the points
+    // held plus a bonus for each card in the strongest suit. Bid the minimum if
+    // the estimate covers it.
+    fn make_bid(&mut self, game: &Game) -> Option<Points> {
+        let bids = game.auction.legal_bids(&game.options);
+        let hand = BotMgr::get_cards(game, game.active_hand());
+        if bids.is_empty() || hand.is_empty() {
+            return None;
+        }
+
+        let suit = self.strongest_suit(&hand);
+        let mut estimate = 0;
+        for card in &hand {
+            estimate += card.points;
+            if card.suit == suit || card.suit.is_special() {
+                estimate += 10;
+            }
+        }
+        estimate = estimate * game.options.hand_size as Points / hand.len() as Points;
+
+        if bids[0] <= estimate {
+            Some(bids[0])
+        } else {
+            None
+        }
+    }
+
+    // Go blind nil when the partnership trails the leaders by more than the
+    // bonus. Otherwise count a trick for each ace and king and for each spade
+    // past the third, and bid nil with none of those and no spade above the 9.
+    fn bid_tricks(&mut self, game: &Game) -> TrickBid {
+        let bids = game.legal_trick_bids();
+        if bids.contains(&TrickBid::BlindNil) {
+            let ours = game.team_of(game.active_player).score();
+            let best = game.teams.iter().map(|team| team.score()).max().unwrap();
+            if best - ours > game.options.spades.blind_nil_bonus {
+                return TrickBid::BlindNil;
+            }
+        }
+
+        let hand = BotMgr::get_cards(game, game.active_hand());
+        let spades = hand.iter().filter(|card| card.suit == CardSuit::Spade);
+        let high_spades = spades.clone().filter(|card| card.face_rank > 9).count();
+        let mut tricks = hand.iter().filter(|card| card.face_rank >= 13).count();
+        tricks += spades.count().saturating_sub(3);
+        if tricks == 0 && high_spades == 0 && bids.contains(&TrickBid::Nil) {
+            return TrickBid::Nil;
+        }
+        TrickBid::Tricks(tricks.clamp(1, game.options.hand_size as usize) as u8)
+    }
+
+    // Count 3 for the right bower, 2 for the left, 2 for the ace or king of
+    // trump, 1.5 for other trump and 1 for each off-suit ace. A dealer being
+    // ordered up counts the turned card too. Make the best suit worth 6, or
+    // the best of any when the dealer is stuck.
+    fn bid_trump(&mut self, game: &Game) -> Option<CardSuit> {
+        let rules = game.rules();
+        let bids = game.legal_trump_bids();
+        let mut hand = BotMgr::get_cards(game, game.active_hand());
+        if game.ordering_up() && game.active_player == game.dealer {
+            hand.push(game.cards[game.turn_up().unwrap()].clone());
+        }
+
+        let mut best = None;
+        let mut best_value = 0.0;
+        for suit in bids.iter().flatten().copied() {
+            let value: f32 = hand
+                .iter()
+                .map(|card| match rules.trump_rank(card, suit) {
+                    Some(rank) if rank > 14.0 => rank - 13.0,
+                    Some(rank) if rank >= 13.0 => 2.0,
+                    Some(_) => 1.5,
+                    None if card.face_rank == 14 => 1.0,
+                    None => 0.0,
+                })
+                .sum();
+            if best.is_none() || value > best_value {
+                best = Some(suit);
+                best_value = value;
+            }
+        }
+        match best_value >= 6.0 || !bids.contains(&None) {
+            true => best,
+            false => None,
+        }
     }
 
-    // Choose a random suit.
-    // fn choose_trump(&self, _game: &Game) -> CardSuit {
-    //     let suits = [
-    //         CardSuit::Club,
-    //         CardSuit::Diamond,
-    //         CardSuit::Heart,
-    //         CardSuit::Spade,
-    //     ];
-    //     let rand_idx = fastrand::usize(0..suits.len());
-    //     suits[rand_idx]
-    // }
+    // Choose the allowed suit with the most rank. If no trump is allowed,
+    // choose it with no long suit and at least three top cards.
+    fn choose_trump(&mut self, game: &Game) -> Option<CardSuit> {
+        let hand = BotMgr::get_cards(game, game.active_hand());
+        let suit = self.strongest_of(&hand, &game.trump_choices());
+        if game.options.trump_rules.allow_no_trump {
+            let longest = hand.iter().filter(|card| card.suit == suit).count();
+            let top_cards = hand
+                .iter()
+                .filter(|card| card.face_rank == game.options.deck.high_rank)
+                .count();
+            if longest * 3 <= hand.len() && top_cards >= 3 {
+                return None;
+            }
+        }
+        Some(suit)
+    }
+
+    // Discard the lowest card outside trump, or the strongest suit before
+    // trump is named, if possible. Keep point cards out of the nest when
+    // they'd score for the defenders.
+    fn discard(&mut self, game: &Game) -> CardId {
+        let hand = BotMgr::get_cards(game, game.active_hand());
+        let suit = game
+            .trump_suit
+            .unwrap_or_else(|| self.strongest_suit(&hand));
+        let mut ids = game.eligible_discards();
+        if game.options.discard_rules.counters_to_defenders {
+            let blanks = BotMgr::ids_without_points(&ids, game);
+            if !blanks.is_empty() {
+                ids = blanks;
+            }
+        }
+        let mut off_suit_ids = BotMgr::ids_without_suit(&ids, suit, game);
+        off_suit_ids.retain(|id| !game.cards[*id].is_trump);
+        let candidates = if off_suit_ids.is_empty() {
+            ids
+        } else {
+            off_suit_ids
+        };
+        BotMgr::lowest_rank(&candidates, game).unwrap()
+    }
+
+    // Pass the dangerous cards: the queen of spades and the spades above it,
+    // then the highest hearts, then the highest of the rest.
+    fn pass_card(&mut self, game: &Game) -> CardId {
+        let danger = |card: &Card| match card.suit {
+            CardSuit::Spade if card.face_rank >= 12 => 200.0 + card.game_rank,
+            CardSuit::Heart => 100.0 + card.game_rank,
+            _ => card.game_rank,
+        };
+        let ids = game.eligible_discards();
+        ids.iter()
+            .copied()
+            .max_by(|a, b| danger(&game.cards[*a]).total_cmp(&danger(&game.cards[*b])))
+            .unwrap()
+    }
+
+    // Call the highest trump not in hand, or the highest card with no trump.
+    fn call_partner(&mut self, game: &Game) -> CardId {
+        let ids = game.callable_card_ids();
+        let not_held: Vec<CardId> = ids
+            .into_iter()
+            .filter(|id| !game.active_hand().contains(id))
+            .collect();
+        let trump_ids = match game.trump_suit {
+            Some(suit) => BotMgr::ids_with_suit(&not_held, suit, game),
+            None => Vec::new(),
+        };
+        match BotMgr::highest_rank(&trump_ids, game) {
+            Some(id) => id,
+            None => BotMgr::highest_rank(&not_held, game).unwrap(),
+        }
+    }
+
+    // Go alone holding trump in at least two thirds of the hand.
+    fn go_alone(&mut self, game: &Game) -> bool {
+        let hand = BotMgr::get_cards(game, game.active_hand());
+        let trump_count = hand.iter().filter(|card| card.is_trump).count();
+        trump_count * 3 >= hand.len() * 2
+    }
+
+    // Notice every other renege.
+    fn call_renege(&mut self, game: &Game) -> Option<PlayerId> {
+        BotMgr::notice_renege(game, &mut self.rng, 2)
+    }
 
     // Use a MonteCarlo simulation to pick the best card.
-    fn play_card(&self, game: &Game) -> CardId {
+    fn play_card(&mut self, game: &Game) -> CardId {
         let monte_player = game.active_player;
 
-        let playable_ids = game.get_playable_card_ids();
+        let playable_ids = game.legal_card_ids();
         if playable_ids.len() == 1 {
             return playable_ids[0];
         }
@@ -62,8 +254,9 @@ impl Bot for BotMonte {
             hidden_cards.push(*id);
         }
 
+        // A player sitting out keeps their cards out of play.
         for p in 0..game.player_count {
-            if p == game.active_player {
+            if p == game.active_player || !game.players[p].active {
                 continue;
             }
             // Push a copy of player's hand id. We don't want to clear the hand using
@@ -75,23 +268,24 @@ impl Bot for BotMonte {
 
         let simulations = 1000;
 
-        let mut id_score = Vec::new();
+        // Summed wider than Points, which 1000 hands of scores would overflow.
+        let mut id_score: Vec<i32> = Vec::new();
         for _ in 0..playable_ids.len() {
             id_score.push(0);
         }
 
-        let random_bot = BotRandom::new();
+        let mut random_bot = BotRandom::new(self.rng.fork());
 
         for _ in 0..simulations {
             let mut sim_game = game.clone();
             let mut cards = hidden_cards.clone();
-            fastrand::shuffle(&mut cards);
+            self.rng.shuffle(&mut cards);
 
             // Assign random cards to all players but the active player.
             // TODO: Keep certain suits from being assigned if previous
             // play has shown the player is out of that suit.
             for (p, player) in sim_game.players.iter_mut().enumerate() {
-                if p == monte_player {
+                if p == monte_player || !player.active {
                     continue;
                 }
                 let card_count = player.hand.len();
@@ -110,34 +304,29 @@ impl Bot for BotMonte {
                 //     println!("{}: {:?}", idx, monte_game.players[idx].partner);
                 // }
 
-                monte_game.play_card_id(id);
+                monte_game.play_card_id(id).expect("Monte play failed");
 
-                while !monte_game.hand_completed() {
-                    while !monte_game.trick_completed() {
-                        // Randomly play subsequent cards.
-                        //println!("active players: {}", monte_game.active_player_count());
-                        //println!("rando ids: {}", monte_game.get_playable_card_ids().len());
-                        let c_id = random_bot.play_card(&monte_game);
-                        monte_game.play_card_id(&c_id);
+                // Randomly play subsequent cards, turn by turn.
+                loop {
+                    if monte_game.trick_completed() {
+                        monte_game.award_trick();
+                        if monte_game.hand_completed() {
+                            break;
+                        }
+                        monte_game.prepare_for_new_trick();
+                    } else {
+                        monte_game.advance_active_player();
                     }
-
-                    monte_game.award_trick();
-                    monte_game.prepare_for_new_trick();
+                    let c_id = random_bot.play_card(&monte_game);
+                    monte_game.play_card_id(&c_id).expect("Monte play failed");
                 }
 
-                let (makers_score, defenders_score) = monte_game.makers_and_defenders_score();
-                id_score[i] += match &monte_game.players[monte_player].kind {
-                    Some(kind) => match kind {
-                        PlayerKind::Maker => makers_score,
-                        PlayerKind::Defender => defenders_score,
-                    },
-                    None => panic!(),
-                };
+                id_score[i] += self.hand_value(&mut monte_game, monte_player) as i32;
             }
         }
 
         // Select the card id with the highest score.
-        let mut highest_score = 0;
+        let mut highest_score = i32::MIN;
         let mut best_id = slotmap::DefaultKey::default();
         for (idx, score) in id_score.iter().enumerate() {
             println!("p: {monte_player}, idx: {}, score: {}", idx, score);
@@ -1,38 +1,96 @@
-use crate::bot::Bot;
-use crate::card::{CardId, CardSuit};
+use crate::bid::TrickBid;
+use crate::bot::{Bot, BotMgr};
+use crate::card::{CardId, CardSuit, Points};
 use crate::game::Game;
+use crate::player::PlayerId;
 
 #[derive(Clone)]
-pub struct BotRandom {}
+pub struct BotRandom {
+    rng: fastrand::Rng,
+}
 
 impl BotRandom {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(rng: fastrand::Rng) -> Self {
+        Self { rng }
     }
 }
 
 impl Bot for BotRandom {
-    // Pass
-    fn make_bid(&self, _game: &Game) -> Option<CardSuit> {
-        None
-    }
-
-    // Choose a random suit.
-    // fn choose_trump(&self, _game: &Game) -> CardSuit {
-    //     let suits = [
-    //         CardSuit::Club,
-    //         CardSuit::Diamond,
-    //         CardSuit::Heart,
-    //         CardSuit::Spade,
-    //     ];
-    //     let rand_idx = fastrand::usize(0..suits.len());
-    //     suits[rand_idx]
-    // }
-
-    // Play a random playable card.
-    fn play_card(&self, game: &Game) -> CardId {
-        let ids = game.get_playable_card_ids();
-        let rand_idx = fastrand::usize(0..ids.len());
+    // Bid the minimum now and then, otherwise pass.
+    fn make_bid(&mut self, game: &Game) -> Option<Points> {
+        let bids = game.auction.legal_bids(&game.options);
+        if bids.is_empty() || self.rng.u8(0..4) > 0 {
+            return None;
+        }
+        Some(bids[0])
+    }
+
+    // Bid nil now and then, otherwise a random 1 to 4 tricks.
+    fn bid_tricks(&mut self, game: &Game) -> TrickBid {
+        let bids = game.legal_trick_bids();
+        let nils: Vec<TrickBid> = bids.iter().copied().filter(|bid| bid.is_nil()).collect();
+        if !nils.is_empty() && self.rng.u8(0..10) == 0 {
+            return nils[self.rng.usize(0..nils.len())];
+        }
+        TrickBid::Tricks(self.rng.u8(1..=4))
+    }
+
+    // Make trump now and then, otherwise pass.
+    fn bid_trump(&mut self, game: &Game) -> Option<CardSuit> {
+        let bids = game.legal_trump_bids();
+        if bids.contains(&None) && self.rng.u8(0..4) > 0 {
+            return None;
+        }
+        let suits: Vec<CardSuit> = bids.into_iter().flatten().collect();
+        Some(suits[self.rng.usize(0..suits.len())])
+    }
+
+    // Choose a random allowed suit, or no trump now and then if allowed.
+    fn choose_trump(&mut self, game: &Game) -> Option<CardSuit> {
+        if game.options.trump_rules.allow_no_trump && self.rng.u8(0..5) == 0 {
+            return None;
+        }
+        let suits = game.trump_choices();
+        let rand_idx = self.rng.usize(0..suits.len());
+        Some(suits[rand_idx])
+    }
+
+    // Discard a random eligible card.
+    fn discard(&mut self, game: &Game) -> CardId {
+        let ids = game.eligible_discards();
+        let rand_idx = self.rng.usize(0..ids.len());
+        ids[rand_idx]
+    }
+
+    // Pass a random card.
+    fn pass_card(&mut self, game: &Game) -> CardId {
+        let ids = game.eligible_discards();
+        let rand_idx = self.rng.usize(0..ids.len());
+        ids[rand_idx]
+    }
+
+    // Call a random card from someone else's hand or the nest.
+    fn call_partner(&mut self, game: &Game) -> CardId {
+        let mut ids = game.callable_card_ids();
+        ids.retain(|id| !game.active_hand().contains(id));
+        let rand_idx = self.rng.usize(0..ids.len());
+        ids[rand_idx]
+    }
+
+    // Go alone now and then.
+    fn go_alone(&mut self, _game: &Game) -> bool {
+        self.rng.u8(0..10) == 0
+    }
+
+    // Notice a renege now and then.
+    fn call_renege(&mut self, game: &Game) -> Option<PlayerId> {
+        BotMgr::notice_renege(game, &mut self.rng, 3)
+    }
+
+    // Play a random legal card. Bots don't renege.
+    fn play_card(&mut self, game: &Game) -> CardId {
+        let ids = game.legal_card_ids();
+        let rand_idx = self.rng.usize(0..ids.len());
         ids[rand_idx]
     }
 }
@@ -9,6 +9,7 @@ pub enum CardSuit {
     Heart,
     Spade,
     Joker,
+    Bird,
 }
 
 impl CardSuit {
@@ -18,12 +19,17 @@ impl CardSuit {
             CardSuit::Diamond => "diamond".to_string(),
             CardSuit::Heart => "heart".to_string(),
             CardSuit::Spade => "spade".to_string(),
-            CardSuit::Joker => panic!(),
+            CardSuit::Joker | CardSuit::Bird => panic!(),
         }
     }
+
+    /// Special cards stand outside the four suits and always count as trump.
+    pub fn is_special(&self) -> bool {
+        matches!(self, CardSuit::Joker | CardSuit::Bird)
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SelectState {
     Selectable,   // Expands a bit in size when mouse over.
     Unselectable, // Normal size and appearance, just unselectable.
@@ -38,11 +44,13 @@ pub type GameRank = f32;
 /// Signed int to allow for negative score in case score system changes.
 pub type Points = i16;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Card {
     pub id: CardId,
     pub suit: CardSuit,
     pub face_rank: FaceRank,
+    /// Which copy of the card this is, from 0, in a deck with duplicates.
+    pub copy: u8,
     pub game_rank: GameRank,
     pub is_trump: bool,
     pub points: Points,
@@ -57,6 +65,7 @@ impl Card {
             id,
             suit,
             face_rank,
+            copy: 0,
             game_rank: face_rank as f32,
             is_trump: false,
             points: 0,
@@ -83,30 +92,52 @@ impl Card {
             CardSuit::Diamond => 200 + rank,
             CardSuit::Heart => 400 + rank,
             CardSuit::Spade => 600 + rank,
-            CardSuit::Joker => 800,
+            CardSuit::Joker => 800 + rank,
+            CardSuit::Bird => 1000 + rank,
+        }
+    }
+
+    /// True if the card follows the lead card's suit. All trump, including the
+    /// special cards, counts as one suit.
+    pub fn follows(&self, lead_card: &Card) -> bool {
+        if lead_card.is_trump {
+            self.is_trump
+        } else {
+            !self.is_trump && self.suit == lead_card.suit
         }
     }
 
     fn rank_string(&self) -> String {
-        match self.game_rank as i8 {
+        match self.face_rank {
             11 => "J".to_string(),
             12 => "Q".to_string(),
             13 => "K".to_string(),
             14 => "A".to_string(),
             _ => {
-                let rank = self.game_rank as usize;
+                let rank = self.face_rank as usize;
                 rank.to_string()
             }
         }
     }
 
+    /// Plain text name for the UI, eg "A heart".
+    pub fn name(&self) -> String {
+        match self.suit {
+            CardSuit::Joker => "Joker".to_string(),
+            CardSuit::Bird => "Bird".to_string(),
+            _ => format!("{} {}", self.rank_string(), self.suit.to_string()),
+        }
+    }
+
+    /// The texture name. Copies of a card share it.
     pub fn file_string(&self) -> String {
         match self.suit {
-            CardSuit::Club => format!("cards/clb{}", self.game_rank as i8),
-            CardSuit::Diamond => format!("cards/dia{}", self.game_rank as i8),
-            CardSuit::Heart => format!("cards/hrt{}", self.game_rank as i8),
-            CardSuit::Spade => format!("cards/spd{}", self.game_rank as i8),
+            CardSuit::Club => format!("cards/clb{}", self.face_rank),
+            CardSuit::Diamond => format!("cards/dia{}", self.face_rank),
+            CardSuit::Heart => format!("cards/hrt{}", self.face_rank),
+            CardSuit::Spade => format!("cards/spd{}", self.face_rank),
             CardSuit::Joker => format!("cards/joker"),
+            CardSuit::Bird => format!("cards/bird"),
         }
     }
 }
@@ -140,6 +171,7 @@ impl core::fmt::Display for Card {
             CardSuit::Diamond => write!(f, "{rank}♦️"),
             CardSuit::Heart => write!(f, "{rank}♥️"),
             CardSuit::Joker => write!(f, "Jk"),
+            CardSuit::Bird => write!(f, "Bd"),
         }
     }
 }
@@ -1,7 +1,9 @@
+use std::f32::consts::PI;
+
 use notan::math::{vec2, Vec2};
 
 use crate::{
-    card::{CardId, SelectState},
+    card::{CardId, Points, SelectState},
     player::PlayerId,
     view_geom::{ViewGeom, CARD_SIZE, VIEW_CENTER},
 };
@@ -30,6 +32,7 @@ pub struct CardUpdate {
     pub player_is_bot: bool,
     pub face_up: bool,
     pub select_state: SelectState,
+    pub points: Points,
     // If delay is > 0.0, then all the other fields are ignored and the update just
     // serves to delay subsequent updates. The delay is reduced each frame
     // until <= 0.0, then discarded.
@@ -48,6 +51,7 @@ impl Default for CardUpdate {
             player_is_bot: false,
             face_up: false,
             select_state: SelectState::Unselectable,
+            points: 0,
             //delay: 0.0,
         }
     }
@@ -90,8 +94,12 @@ impl CardUpdate {
     }
 
     pub fn hand_card_translation(&self) -> Vec2 {
+        let distance_from_center = 300.0; //if self.mouse_over { 270.0 } else { 300.0 };
+
+        // Bot hands shrink to fit between their neighbors as seats are added.
+        let seat_width = 2.0 * distance_from_center * (PI / self.player_len as f32).sin();
         let max_width = match self.player_is_bot {
-            true => 280.,
+            true => seat_width * 0.66,
             false => 500.,
         };
         let max_spacing: f32 = 85.;
@@ -102,8 +110,6 @@ impl CardUpdate {
         let mut x_offset = (self.group_len - 1) as f32 * -x_spacing / 2.0;
         x_offset += self.group_index as f32 * x_spacing;
 
-        let distance_from_center = 300.0; //if self.mouse_over { 270.0 } else { 300.0 };
-
         let radians = ViewGeom::player_radians_from_center(self.player, self.player_len);
         let mut pos = ViewGeom::position_from(VIEW_CENTER, radians, distance_from_center);
 
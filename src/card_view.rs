@@ -112,6 +112,15 @@ impl CardView {
 
         self.z_order = location.z_order();
     }
+
+    /// Move straight to the location, stopping any animation.
+    pub fn place_at(&mut self, location: CardUpdate) {
+        self.translation_animator = None;
+        self.angle_animator = None;
+        self.transform.set_translation(location.translation());
+        self.transform.set_angle(location.angle());
+        self.z_order = location.z_order();
+    }
 }
 
 impl ViewTrait for CardView {
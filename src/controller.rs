@@ -11,6 +11,7 @@ use crate::card::{CardId, SelectState};
 use crate::card_update::{CardGroup, CardUpdate};
 use crate::game::{Game, GameAction, PlayerAction};
 use crate::player::PlayerId;
+use crate::save;
 use crate::trick::Trick;
 use crate::view::View;
 use crate::view_trait::ViewTrait;
@@ -39,11 +40,15 @@ pub struct Controller {
 }
 
 impl Controller {
-    pub fn new(assets: &mut Assets, gfx: &mut Graphics) -> Self {
+    /// game is new, or loaded from a save file to resume.
+    pub fn new(assets: &mut Assets, gfx: &mut Graphics, mut game: Game) -> Self {
         let (player_action_sender, player_action_receiver) = mpsc::channel();
 
-        let mut game = Game::new();
-        game.do_next_action();
+        // A loaded game is stopped at an action. A new one hasn't started.
+        let resuming = game.awaiting.is_some();
+        if !resuming {
+            game.do_next_action();
+        }
 
         // Game clone speed test
         // let now = std::time::Instant::now();
@@ -62,7 +67,7 @@ impl Controller {
         let (audio_message_sender, audio_message_receiver) = mpsc::channel();
         *AUDIO_SENDER.lock().unwrap() = Some(audio_message_sender);
 
-        Self {
+        let mut controller = Self {
             game,
             player_action_sender,
             player_action_receiver,
@@ -72,6 +77,69 @@ impl Controller {
 
             card_updates: VecDeque::new(),
             game_action_delay: 0.0,
+        };
+        if resuming {
+            controller.resume();
+        }
+        controller
+    }
+
+    /// Bring the view up to date with a loaded game. Cards are placed without
+    /// animating, then the action the game stopped at is taken up again.
+    fn resume(&mut self) {
+        let awaiting = self.game.awaiting.clone();
+
+        self.view
+            .update_dealer(self.game.dealer, self.game.player_count);
+        self.view
+            .update_active_player(self.game.active_player, self.game.player_count);
+        for bid in &self.game.auction.history {
+            self.view.set_bid(bid.player, bid.points);
+        }
+        for (p, bid) in self.game.trick_bids.iter().enumerate() {
+            if let Some(bid) = bid {
+                self.view.set_trick_bid(p, *bid);
+            }
+        }
+        match self.game.trump_suit {
+            Some(suit) => self.view.set_trump(Some(suit)),
+            // A maker in play with no trump suit chose no trump.
+            None if self.game.maker.is_some()
+                && matches!(awaiting, Some(GameAction::WaitForPlayCard(_))) =>
+            {
+                self.view.set_trump(None)
+            }
+            None => {}
+        }
+        if let Some(GameAction::WaitForPlayCard(_)) = awaiting {
+            self.view
+                .show_renege_buttons(self.game.options.renege.allowed);
+        }
+        self.view.update_call_text(&self.game);
+        self.view.update_scores(&self.game);
+
+        // Later updates win, so hands go last in case passed cards were received.
+        self.update_deck();
+        if let Some(action) = &awaiting {
+            self.update_nest(action);
+        }
+        self.update_passes();
+        for p in 0..self.game.player_count {
+            for trick in self.game.players[p].tricks.clone() {
+                self.update_won_trick(&trick);
+            }
+        }
+        self.update_active_trick();
+        self.update_hands();
+        self.view.place_cards(&mut self.card_updates);
+
+        self.game.actions_taken.extend(awaiting);
+    }
+
+    fn save_game(&self) {
+        match save::write_to_yaml(&self.game, save::SAVE_PATH) {
+            Ok(()) => println!("Game saved to {}", save::SAVE_PATH),
+            Err(e) => println!("Error saving game: {}", e),
         }
     }
 
@@ -85,7 +153,7 @@ impl Controller {
                 self.view
                     .handle_mouse_event(&event, screen_pt, &affine, true);
             }
-            // Event::KeyDown { key }
+            Event::KeyDown { key: KeyCode::S } => self.save_game(),
             _ => {}
         };
     }
@@ -104,6 +172,14 @@ impl Controller {
                         self.game_action_delay = 0.5;
                     }
                     GameAction::PrepareForNewHand => {
+                        self.view.clear_bids();
+                        self.view.clear_trump();
+                        if let Some(suit) = self.game.rules().fixed_trump() {
+                            self.view.set_trump(Some(suit));
+                        }
+                        self.view.update_call_text(&self.game);
+                        self.view.update_scores(&self.game);
+                        self.view.deal_button.visible = true;
                         self.update_deck();
                         self.view
                             .update_dealer(self.game.dealer, self.game.player_count);
@@ -126,18 +202,69 @@ impl Controller {
                             self.view.get_bid(&self.game);
                         }
                     }
+                    GameAction::WaitForTrickBid => {
+                        self.view
+                            .update_active_player(self.game.active_player, self.game.player_count);
+                        if self.game.active_player_is_bot() {
+                            self.spawn_bid_tricks_bot();
+                            self.game_action_delay = 0.5;
+                        } else {
+                            self.view.get_trick_bid(&self.game);
+                        }
+                    }
+                    GameAction::WaitForTrumpBid => {
+                        // The turned card goes face down for the second round.
+                        self.update_nest(&action);
+                        self.view
+                            .update_active_player(self.game.active_player, self.game.player_count);
+                        if self.game.active_player_is_bot() {
+                            self.spawn_bid_trump_bot();
+                            self.game_action_delay = 0.5;
+                        } else {
+                            self.view.get_trump_bid(&self.game);
+                        }
+                    }
+                    GameAction::ThrowIn => {
+                        self.view.show_message("All passed. Redeal.");
+                        self.game_action_delay = 1.5;
+                    }
+                    GameAction::StickDealer => {
+                        self.view.set_bid(self.game.dealer, self.game.contract());
+                        self.view.show_message("All passed. The dealer is stuck.");
+                        self.game_action_delay = 1.5;
+                    }
+                    GameAction::ReopenBidding(minimum) => {
+                        self.view.clear_bids();
+                        self.view
+                            .show_message(&format!("All passed. Bidding reopens at {minimum}."));
+                        self.game_action_delay = 1.5;
+                    }
                     GameAction::MoveNestToHand => {
+                        self.view.clear_message();
                         self.update_hands();
                         self.update_nest(&action);
                     }
+                    GameAction::PreparePass => {
+                        self.view.clear_message();
+                    }
                     GameAction::WaitForDiscards => {
+                        self.view
+                            .update_active_player(self.game.active_player, self.game.player_count);
                         if self.game.active_player_is_bot() {
-                            //self.spawn_make_bid_bot();
+                            self.spawn_discard_bot();
+                            self.game_action_delay = 0.5;
                         } else {
+                            if self.game.passing {
+                                self.view.show_message(&format!(
+                                    "Pass {} cards {}.",
+                                    self.game.exchange_size(),
+                                    self.game.pass_direction().name()
+                                ));
+                            }
                             self.view.get_discard(&self.game);
                         }
                     }
-                    GameAction::MoveCardToDiscard(..) => {
+                    GameAction::MoveCardToDiscard(..) | GameAction::ReturnDiscardToHand(..) => {
                         self.update_hands();
                         self.update_nest(&action);
                     }
@@ -149,11 +276,47 @@ impl Controller {
                     GameAction::EndNestExchange => {
                         self.view.end_discard();
                         self.update_nest(&action);
+                        self.update_passes();
+                    }
+                    GameAction::ReceivePasses => {
+                        self.view.clear_message();
+                        self.update_hands();
+                        self.game_action_delay = 1.0;
+                    }
+                    GameAction::WaitForChooseTrump => {
+                        self.view
+                            .update_active_player(self.game.active_player, self.game.player_count);
+                        if self.game.active_player_is_bot() {
+                            self.spawn_choose_trump_bot();
+                            self.game_action_delay = 0.5;
+                        } else {
+                            self.view.get_trump(&self.game);
+                        }
+                    }
+                    GameAction::WaitForCallPartner => {
+                        if self.game.active_player_is_bot() {
+                            self.spawn_call_partner_bot();
+                            self.game_action_delay = 0.5;
+                        } else {
+                            self.view.get_call_partner(&self.game);
+                        }
+                    }
+                    GameAction::WaitForGoAlone => {
+                        if self.game.active_player_is_bot() {
+                            self.spawn_go_alone_bot();
+                            self.game_action_delay = 0.5;
+                        } else {
+                            self.view.get_go_alone(&self.game);
+                        }
                     }
                     GameAction::PrepareForNewTrick => {
                         self.update_hands();
                     }
                     GameAction::PrePlayCard => {
+                        self.view.clear_message();
+                        self.view
+                            .show_renege_buttons(self.game.options.renege.allowed);
+                        self.view.update_call_text(&self.game);
                         self.update_hands();
                         self.update_active_trick();
                         self.game_action_delay = 1.0;
@@ -171,8 +334,15 @@ impl Controller {
                     GameAction::AwardTrick(trick) => {
                         self.update_won_trick(trick);
                     },
-                    GameAction::EndHand => todo!(),
-                    GameAction::EndGame => todo!(),
+                    GameAction::EndHand => {
+                        self.view.show_renege_buttons(false);
+                        self.view.update_call_text(&self.game);
+                        self.view.update_scores(&self.game);
+                        self.game_action_delay = 2.0;
+                    }
+                    GameAction::EndGame => {
+                        self.view.show_game_over(&self.game);
+                    }
                 }
             }
         }
@@ -184,29 +354,79 @@ impl Controller {
         // Check for PlayerAction messages and call related game functions.
         let received = self.player_action_receiver.try_recv();
         if let Ok(action) = received {
-            match action {
-                PlayerAction::DealCards => {
-                    self.view.deal_button.visible = false;
-                }
-                PlayerAction::MakeBid(opt_suit) => {
-                    self.view.set_trump(opt_suit);
-                    self.view.bid_selector.visible = false;
-                }
-                PlayerAction::PlayCard(_, _) => {
-                    self.view.end_card_play();
+            let active_player = self.game.active_player;
+            match self.game.perform_player_action(&action) {
+                Ok(()) => {
+                    self.show_player_action(action, active_player);
+                    self.game.do_next_action();
                 }
-                PlayerAction::MoveCardToNest(_) => {}
-                PlayerAction::TakeCardFromNest(_) => todo!(),
-                PlayerAction::EndNestExchange => todo!(),
+                Err(e) => println!("Rejected {:?}: {}", action, e),
             }
-            self.game.perform_player_action(&action);
-            self.game.do_next_action();
         }
 
         self.view.update(time_delta, app);
         self.update_sounds(app);
     }
 
+    /// Update the view for a PlayerAction the game accepted. active_player
+    /// is who was active before the action.
+    fn show_player_action(&mut self, action: PlayerAction, active_player: PlayerId) {
+        match action {
+            PlayerAction::DealCards => {
+                self.view.deal_button.visible = false;
+                self.view.clear_message();
+            }
+            PlayerAction::MakeBid(points) => {
+                self.view.clear_message();
+                self.view.set_bid(active_player, points);
+                self.view.bid_selector.visible = false;
+            }
+            PlayerAction::BidTricks(bid) => {
+                self.view.clear_message();
+                self.view.set_trick_bid(active_player, bid);
+                self.view.bid_selector.visible = false;
+                self.update_hands();
+            }
+            PlayerAction::LookAtHand => {
+                self.view.bid_selector.visible = false;
+                self.update_hands();
+            }
+            PlayerAction::BidTrump(suit) => {
+                self.view.set_trump_bid(active_player, suit);
+                if suit.is_some() {
+                    self.view.set_trump(suit);
+                }
+                self.view.bid_selector.visible = false;
+            }
+            PlayerAction::ChooseTrump(suit) => {
+                self.view.set_trump(suit);
+                self.view.bid_selector.visible = false;
+            }
+            PlayerAction::CallPartner(_) => {
+                self.view.bid_selector.visible = false;
+            }
+            PlayerAction::CallRenege(caller, accused) => {
+                let correct = self.game.renege_calls.last().map_or(false, |call| call.correct);
+                self.view.show_message(&match correct {
+                    true => format!("P{caller} caught P{accused} reneging!"),
+                    false => format!("P{caller} wrongly called a renege on P{accused}."),
+                });
+            }
+            PlayerAction::GoAlone(_) => {
+                self.view.bid_selector.visible = false;
+                self.view.update_call_text(&self.game);
+                self.update_hands();
+            }
+            PlayerAction::PlayCard(_, _) => {
+                self.view.end_card_play();
+            }
+            PlayerAction::MoveCardToNest(_) | PlayerAction::TakeCardFromNest(_) => {}
+            PlayerAction::EndNestExchange => {
+                self.view.end_discard();
+            }
+        }
+    }
+
     fn update_deck(&mut self) {
         let mut update = CardUpdate {
             group: CardGroup::Deck,
@@ -217,6 +437,7 @@ impl Controller {
             update.group_index = idx;
             if let Some(card) = self.game.cards.get(*id) {
                 update.face_up = card.face_up;
+                update.points = card.points;
                 update.select_state = card.select_state;
             }
             self.card_updates.push_back(update.clone());
@@ -238,6 +459,7 @@ impl Controller {
             update.group_index = idx;
             if let Some(card) = self.game.cards.get(*id) {
                 update.face_up = card.face_up;
+                update.points = card.points;
                 update.select_state = card.select_state;
             }
             self.card_updates.push_back(update.clone());
@@ -262,6 +484,7 @@ impl Controller {
                 update.group_index = idx;
                 if let Some(card) = self.game.cards.get(*id) {
                     update.face_up = card.face_up;
+                    update.points = card.points;
                     update.select_state = card.select_state;
                 }
                 self.card_updates.push_back(update.clone());
@@ -276,15 +499,17 @@ impl Controller {
             | GameAction::DealCard(..)
             | GameAction::DealToNest
             | GameAction::WaitForBid
+            | GameAction::WaitForTrumpBid
             | GameAction::MoveNestToHand
             | GameAction::WaitForDiscards
             | GameAction::MoveCardToDiscard(..)
+            | GameAction::ReturnDiscardToHand(..)
             | GameAction::PauseAfterDiscard => CardGroup::NestExchange,
             _ => CardGroup::NestAside,
         };
         let mut update = CardUpdate {
             group: group,
-            group_len: self.game.options.nest_size as usize,
+            group_len: self.game.exchange_size(),
             ..Default::default()
         };
         for (idx, id) in self.game.nest.iter().enumerate() {
@@ -292,12 +517,34 @@ impl Controller {
             update.group_index = idx;
             if let Some(card) = self.game.cards.get(*id) {
                 update.face_up = card.face_up;
+                update.points = card.points;
                 update.select_state = card.select_state;
             }
             self.card_updates.push_back(update.clone());
         }
     }
 
+    /// Passed cards wait face down in front of the passer until everyone
+    /// has passed.
+    fn update_passes(&mut self) {
+        if !self.game.passing {
+            return;
+        }
+        for (p, passed) in self.game.passes.iter().enumerate() {
+            let mut update = CardUpdate {
+                group: CardGroup::TrickAside,
+                player: p,
+                player_len: self.game.player_count,
+                ..Default::default()
+            };
+            for id in passed {
+                update.id = *id;
+                update.face_up = false;
+                self.card_updates.push_back(update.clone());
+            }
+        }
+    }
+
     fn update_active_trick(&mut self) {
         let mut update = CardUpdate {
             group: CardGroup::TrickActive,
@@ -310,6 +557,7 @@ impl Controller {
                 update.player = p;
                 if let Some(card) = self.game.cards.get(*id) {
                     update.face_up = card.face_up;
+                    update.points = card.points;
                 }
                 self.card_updates.push_back(update.clone());
             }
@@ -346,16 +594,61 @@ impl Controller {
     }
 
     // Turn the bot loose on the world.
-    // fn spawn_choose_trump_bot(&self) {
-    //     if !self.game.active_player_is_bot() {
-    //         return;
-    //     }
-    //     let game_clone = self.game.clone();
-    //     let sender = self.player_action_sender.clone();
-    //     std::thread::spawn(move || {
-    //         BotMgr::choose_trump(&game_clone, sender);
-    //     });
-    // }
+    fn spawn_bid_tricks_bot(&self) {
+        let game_clone = self.game.clone();
+        let sender = self.player_action_sender.clone();
+        std::thread::spawn(move || {
+            BotMgr::bid_tricks(&game_clone, sender);
+        });
+    }
+
+    // Turn the bot loose on the world.
+    fn spawn_bid_trump_bot(&self) {
+        let game_clone = self.game.clone();
+        let sender = self.player_action_sender.clone();
+        std::thread::spawn(move || {
+            BotMgr::bid_trump(&game_clone, sender);
+        });
+    }
+
+    // Turn the bot loose on the world.
+    fn spawn_choose_trump_bot(&self) {
+        if !self.game.active_player_is_bot() {
+            return;
+        }
+        let game_clone = self.game.clone();
+        let sender = self.player_action_sender.clone();
+        std::thread::spawn(move || {
+            BotMgr::choose_trump(&game_clone, sender);
+        });
+    }
+
+    // Turn the bot loose on the world.
+    fn spawn_call_partner_bot(&self) {
+        let game_clone = self.game.clone();
+        let sender = self.player_action_sender.clone();
+        std::thread::spawn(move || {
+            BotMgr::call_partner(&game_clone, sender);
+        });
+    }
+
+    // Turn the bot loose on the world.
+    fn spawn_go_alone_bot(&self) {
+        let game_clone = self.game.clone();
+        let sender = self.player_action_sender.clone();
+        std::thread::spawn(move || {
+            BotMgr::go_alone(&game_clone, sender);
+        });
+    }
+
+    // Turn the bot loose on the world.
+    fn spawn_discard_bot(&self) {
+        let game_clone = self.game.clone();
+        let sender = self.player_action_sender.clone();
+        std::thread::spawn(move || {
+            BotMgr::discard(&game_clone, sender);
+        });
+    }
 
     // Turn the bot loose on the world.
     fn spawn_play_card_bot(&self) {
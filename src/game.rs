@@ -1,36 +1,63 @@
 use std::collections::VecDeque;
 
+use serde::{Deserialize, Serialize};
 use slotmap::SlotMap;
 
+use crate::bid::{Auction, Bid, TrickBid};
 use crate::bot::BotKind;
-use crate::card::{Card, CardId, CardSuit, Points, SelectState};
+use crate::card::{Card, CardId, CardSuit, GameRank, Points, SelectState};
 use crate::game::GameAction::*;
-use crate::game_options::{GameOptions, PointsAwarded};
+use crate::game_error::GameError;
+use crate::game_options::{
+    AllPassPolicy, BidTiming, GameOptions, NestDeal, NestOwner, PartnerKind, TieBreak,
+};
 use crate::player::{Player, PlayerId, PlayerKind};
+use crate::rules::{Phase, Rules};
+use crate::rules_hearts::PassDirection;
+use crate::scoring::{HandSummary, ScoreBreakdown, ScoreItem, SideTake};
+use crate::team::{ScoresheetRow, Team};
 use crate::trick::Trick;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum PlayerAction {
     DealCards,
-    MakeBid(Option<CardSuit>), // None = pass
+    MakeBid(Option<Points>), // None = pass
+    BidTricks(TrickBid),
+    LookAtHand, // before bidding, when blind nil is allowed
+    BidTrump(Option<CardSuit>), // None = pass
+    ChooseTrump(Option<CardSuit>), // None = no trump
+    CallPartner(CardId),
+    GoAlone(bool),
+    CallRenege(PlayerId, PlayerId), // caller, accused
     MoveCardToNest(CardId),
     TakeCardFromNest(CardId),
     EndNestExchange,
     PlayCard(PlayerId, CardId),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum GameAction {
     Setup,
     PrepareForNewHand,
     DealCard(PlayerId, Vec<CardId>),
     DealToNest,
     WaitForBid, // player ui or bot launch
+    WaitForTrickBid, // player ui or bot launch
+    WaitForTrumpBid, // player ui or bot launch
+    ThrowIn,    // everyone passed, redeal
+    StickDealer,
+    ReopenBidding(Points),
     MoveNestToHand,
+    PreparePass,
     WaitForDiscards, // player ui or bot launch
     MoveCardToDiscard(CardId),
+    ReturnDiscardToHand(CardId),
     PauseAfterDiscard,
     EndNestExchange,
+    ReceivePasses,
+    WaitForChooseTrump, // player ui or bot launch
+    WaitForCallPartner, // player ui or bot launch
+    WaitForGoAlone,     // player ui or bot launch
     PrepareForNewTrick,
     PrePlayCard,
     WaitForPlayCard(PlayerId), // player ui or bot launch
@@ -40,14 +67,51 @@ pub enum GameAction {
     EndGame,
 }
 
-const DEAL_PAUSES: [u8; 5] = [20, 24, 28, 32, 36];
+/// What happened in the last completed hand, by player.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct HandResult {
+    pub trick_points: Vec<Points>,
+    pub nest_points: Vec<Points>,
+    pub breakdowns: Vec<ScoreBreakdown>,
+    pub scores: Vec<Points>,
+}
+
+/// A card played against the rules of play. See GameOptions::renege.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Revoke {
+    pub player: PlayerId,
+    pub card: CardId,
+    /// The trick's number within the hand.
+    pub trick: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RenegeCall {
+    pub caller: PlayerId,
+    pub accused: PlayerId,
+    /// The accused had revoked this hand.
+    pub correct: bool,
+}
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Game {
     pub options: GameOptions,
 
+    /// From GameOptions::seed, or picked at random.
+    pub seed: u64,
+    /// Shuffles the deck and seeds the bots.
+    #[serde(with = "crate::save::rng_state")]
+    pub rng: fastrand::Rng,
+    /// Drawn from rng each time the game stops for a PlayerAction. The bot
+    /// taking the action seeds its own Rng with it.
+    pub bot_seed: u64,
+
     pub next_action: Option<GameAction>,
+    /// Not saved. A loaded game's view is rebuilt from the rest of its state.
+    #[serde(skip)]
     pub actions_taken: VecDeque<GameAction>,
+    /// The action the game stopped at to wait for a PlayerAction.
+    pub awaiting: Option<GameAction>,
 
     pub cards: SlotMap<CardId, Card>,
     pub deck: Vec<CardId>,
@@ -55,34 +119,76 @@ pub struct Game {
 
     pub player_count: PlayerId,
     pub players: Vec<Player>,
+    /// Fixed for the game, from GameOptions::partner_kind. Each keeps a scoresheet.
+    pub teams: Vec<Team>,
     pub dealer: PlayerId,
     pub active_player: PlayerId,
 
+    /// Who gets each card of this hand's deal, in order. None is the nest.
+    pub deal_order: Vec<Option<PlayerId>>,
+    /// The deal counts at which dealing pauses for bidding. The last is the
+    /// end of the deal.
+    pub deal_pauses: Vec<usize>,
     pub deal_pause_idx: usize,
-    pub deal_count: u8,
+    pub deal_count: usize,
     pub dealing_completed: bool,
 
-    pub pass_count: u8,
+    pub auction: Auction,
+    /// Bidding turns left before dealing resumes at a deal pause.
+    pub bid_turns_left: usize,
+    /// Each player's bid with Phase::TrickBidding.
+    pub trick_bids: Vec<Option<TrickBid>>,
+    /// Which players have seen their cards. With blind nil allowed, a human's
+    /// cards stay face down until they look.
+    pub looked: Vec<bool>,
+    /// Passes so far with Phase::TrumpBidding. The second round starts after
+    /// a full round of them.
+    pub trump_passes: usize,
 
     pub maker: Option<PlayerId>,
+    /// None before trump is chosen, or for a no trump hand.
     pub trump_suit: Option<CardSuit>,
+    /// With PartnerKind::Called, the card named by the maker.
+    pub called_card: Option<CardId>,
+    /// The called card has been played, or the hand is over.
+    pub partner_revealed: bool,
 
     pub trick: Trick,
+    /// Set when the first Joker of the hand is played. See GameOptions::later_joker.
+    pub joker_played: bool,
+    /// Set when the first trump of the hand is played. See TrumpRules::trump_must_be_broken.
+    pub trump_broken: bool,
+    /// Card points the maker buried in the nest. See DiscardRules::counters_to_defenders.
+    pub buried_points: Points,
+    /// The cards the maker buried in the nest, before the rest of the deck
+    /// joined them. See DiscardRules::no_trump.
+    pub buried: Vec<CardId>,
+    /// The maker is playing without their partners. See GameOptions::alone_multiplier.
+    pub alone: bool,
+    /// Every revoke this hand, whether called or not.
+    pub revokes: Vec<Revoke>,
+    pub renege_calls: Vec<RenegeCall>,
+    /// The pass is under way. It runs through the nest exchange one player at a time.
+    pub passing: bool,
+    /// The cards each player passed this hand.
+    pub passes: Vec<Vec<CardId>>,
     pub last_trick_winner: PlayerId,
     pub tricks_played: u8,
 
+    /// Hands scored so far.
+    pub hands_played: usize,
+    /// Kept until the next hand ends.
+    pub last_hand: Option<HandResult>,
     pub game_over: bool,
+    pub winners: Vec<PlayerId>,
 }
 
 impl Game {
-    pub fn new() -> Self {
-        // Write over the defaults, if needed.
-        let options = GameOptions::new();
-        options.write_to_yaml("default.txt");
-
-        // Read as normal.
-        let options = GameOptions::read_from_yaml("default.txt");
-        let player_count = 4;
+    pub fn with_options(options: GameOptions) -> Self {
+        let player_count = options.player_count;
+        if let Err(e) = options.validate() {
+            panic!("Invalid GameOptions: {}", e);
+        }
 
         let mut players = Vec::new();
         for p in 0..player_count {
@@ -98,29 +204,58 @@ impl Game {
         let mut action_queue = VecDeque::new();
         action_queue.push_front(GameAction::PrepareForNewHand);
 
+        let bid_minimum = options.bid_minimum;
+        let teams = Team::teams_for(options.partner_kind, player_count);
+        let seed = options.seed.unwrap_or_else(|| fastrand::u64(..));
+        println!("Seed: {seed}");
         Self {
             options,
+            seed,
+            rng: fastrand::Rng::with_seed(seed),
+            bot_seed: 0,
             next_action: Some(Setup),
             actions_taken: VecDeque::new(),
+            awaiting: None,
             cards: SlotMap::new(),
             deck: Vec::new(),
             nest: Vec::new(),
             player_count,
             players,
+            teams,
             dealer: 2,
 
+            deal_order: Vec::new(),
+            deal_pauses: Vec::new(),
             deal_pause_idx: 0,
             deal_count: 0,
             dealing_completed: false,
 
             active_player: 0,
-            pass_count: 0,
+            auction: Auction::new(player_count, bid_minimum),
+            bid_turns_left: 0,
+            trick_bids: Vec::new(),
+            looked: Vec::new(),
+            trump_passes: 0,
             trump_suit: None,
+            called_card: None,
+            partner_revealed: false,
             maker: None,
             trick: Trick::new(player_count),
+            joker_played: false,
+            trump_broken: false,
+            buried_points: 0,
+            buried: Vec::new(),
+            alone: false,
+            revokes: Vec::new(),
+            renege_calls: Vec::new(),
+            passing: false,
+            passes: Vec::new(),
             last_trick_winner: 0,
             tricks_played: 0,
+            hands_played: 0,
+            last_hand: None,
             game_over: false,
+            winners: Vec::new(),
         }
     }
 
@@ -144,55 +279,71 @@ impl Game {
         self.players[player].bot_kind.is_some()
     }
 
+    /// Players sitting out the hand are skipped.
     pub fn advance_active_player(&mut self) {
         self.active_player = (self.active_player + 1) % self.player_count;
+        while !self.players[self.active_player].active {
+            self.active_player = (self.active_player + 1) % self.player_count;
+        }
         println!("Active P:{}", self.active_player);
     }
 
-    pub fn assign_across_partners(&mut self) {
-        if self.player_count != 4 {
-            panic!("Can't assign partners for {} players.", self.player_count);
+    /// Seat partnerships for PartnerKind::Across and PartnerKind::Alternate,
+    /// from the teams.
+    pub fn assign_fixed_partners(&mut self) {
+        for team in &self.teams {
+            for p in &team.players {
+                self.players[*p].partners =
+                    team.players.iter().copied().filter(|q| q != p).collect();
+            }
         }
-        self.players[0].partner = Some(2);
-        self.players[1].partner = Some(3);
-        self.players[2].partner = Some(0);
-        self.players[3].partner = Some(1);
         println!("partners assigned");
     }
 
-    // fn assign_called_partner(&mut self, caller: PlayerId, card_id: CardId) {
-    //     for p in 0..self.player_count {
-    //         if self.players[p].hand.contains(&card_id) {
-    //             self.players[p].partner = Some(caller);
-    //             self.players[caller].partner = Some(p);
-    //         }
-    //     }
-    // }
+    pub fn team_of(&self, p: PlayerId) -> &Team {
+        self.teams.iter().find(|team| team.contains(p)).unwrap()
+    }
 
-    pub fn create_cards(&mut self) {
-        let mut cards = self.create_card_ranks(5, 14);
+    fn assign_called_partner(&mut self, caller: PlayerId, partner: PlayerId) {
+        if partner != caller {
+            self.players[partner].partners = vec![caller];
+            self.players[caller].partners = vec![partner];
+        }
+    }
 
-        // Remove 6s.
-        cards.retain(|card| card.face_rank != 6);
+    /// Cards the maker may call. Calling one they hold or one in the nest
+    /// means going alone.
+    pub fn callable_card_ids(&self) -> Vec<CardId> {
+        let mut ids: Vec<CardId> = self
+            .cards
+            .values()
+            .filter(|card| !card.suit.is_special())
+            .map(|card| card.id)
+            .collect();
+        ids.sort_by(|a, b| self.cards[*b].cmp(&self.cards[*a]));
+        ids
+    }
 
-        // Assign card points.
-        let rank_points = vec![(5, 5), (10, 10), (14, 15)];
-        for (rank, points) in rank_points {
-            for card in &mut cards {
-                if card.face_rank == rank {
-                    card.points = points;
-                }
-            }
-        }
+    pub fn call_partner(&mut self, id: CardId) {
+        self.called_card = Some(id);
+        self.partner_revealed = false;
+        println!("P:{} calls {}", self.maker.unwrap(), self.cards[id]);
+    }
 
-        // Add Jokers. In this game the rank and value of the two Jokers depend on
-        // which one is played first, so we'll set the values for the first-played
-        // and then change the second Joker after the first is played.
-        for _ in 0..2 {
-            let mut joker = Card::new(CardSuit::Joker, 15);
-            joker.points = 0;
-            cards.push(joker);
+    /// Called when the called card is played (by player p), or with None at the
+    /// end of the hand if the card was in the nest.
+    fn reveal_called_partner(&mut self, p: Option<PlayerId>) {
+        let maker = self.maker.unwrap();
+        if let Some(p) = p {
+            self.assign_called_partner(maker, p);
         }
+        self.partner_revealed = true;
+        self.assign_makers_and_defenders();
+    }
+
+    /// Builds the deck described by GameOptions::deck.
+    pub fn create_cards(&mut self) {
+        let cards = self.rules().create_cards(&self.options);
 
         // Assign IDs.
         for card in cards {
@@ -203,15 +354,31 @@ impl Game {
         }
     }
 
-    fn create_card_ranks(&self, from_rank: u8, to_rank: u8) -> Vec<Card> {
-        let mut cards = Vec::new();
-        for rank in from_rank..=to_rank {
-            cards.push(Card::new(CardSuit::Club, rank));
-            cards.push(Card::new(CardSuit::Diamond, rank));
-            cards.push(Card::new(CardSuit::Heart, rank));
-            cards.push(Card::new(CardSuit::Spade, rank));
+    pub fn rules(&self) -> Box<dyn Rules> {
+        self.options.rules.rules()
+    }
+
+    /// The action that starts the phase following after in the rules' phases.
+    fn next_phase(&self, after: Phase) -> GameAction {
+        let phases = self.rules().phases(&self.options);
+        let idx = phases.iter().position(|phase| *phase == after).unwrap();
+        match phases.get(idx + 1) {
+            Some(Phase::Deal) => PrepareForNewHand,
+            Some(Phase::PassCards) => PreparePass,
+            Some(Phase::Bidding) => WaitForBid,
+            Some(Phase::TrickBidding) => WaitForTrickBid,
+            Some(Phase::TrumpBidding) => WaitForTrumpBid,
+            Some(Phase::NestExchange) => MoveNestToHand,
+            Some(Phase::ChooseTrump) => WaitForChooseTrump,
+            Some(Phase::CallPartner) => WaitForCallPartner,
+            Some(Phase::GoAlone) => WaitForGoAlone,
+            Some(Phase::Play) => PrepareForNewTrick,
+            None => EndHand,
         }
-        cards
+    }
+
+    fn has_phase(&self, phase: Phase) -> bool {
+        self.rules().phases(&self.options).contains(&phase)
     }
 
     pub fn prepare_for_new_hand(&mut self) {
@@ -221,31 +388,122 @@ impl Game {
 
         // Put all the ids in the deck and shuffle.
         self.deck = self.cards.keys().collect();
-        fastrand::shuffle(&mut self.deck);
+        self.rng.shuffle(&mut self.deck);
+        for card in self.cards.values_mut() {
+            card.face_up = false;
+            card.is_trump = false;
+            // Undo last hand's bowers. The Jokers are reset below.
+            if !card.suit.is_special() {
+                card.game_rank = card.face_rank as GameRank;
+            }
+            card.select_state = SelectState::Unselectable;
+        }
 
         self.nest.clear();
 
         self.dealer = (self.dealer + 1) % self.player_count;
 
+        self.plan_deal();
+        self.deal_pause_idx = 0;
+        self.deal_count = 0;
+        self.dealing_completed = false;
+
         self.active_player = (self.dealer + 1) % self.player_count;
 
-        self.pass_count = 0;
+        self.auction = Auction::new(self.player_count, self.options.bid_minimum);
+        self.bid_turns_left = 0;
+        self.trick_bids = vec![None; self.player_count];
+        self.looked = vec![false; self.player_count];
+        self.trump_passes = 0;
+        self.maker = None;
+        self.trump_suit = None;
+        if let Some(suit) = self.rules().fixed_trump() {
+            self.set_trump(Some(suit));
+        }
+        self.trump_broken = false;
+        self.buried_points = 0;
+        self.buried.clear();
+        self.alone = false;
+        self.revokes.clear();
+        self.renege_calls.clear();
+        self.passing = false;
+        self.passes.clear();
 
         self.trick = Trick::new(self.player_count);
         self.tricks_played = 0;
+        self.reset_jokers();
+
+        self.called_card = None;
+        self.partner_revealed = false;
+        match self.options.partner_kind {
+            PartnerKind::Across | PartnerKind::Alternate => self.assign_fixed_partners(),
+            PartnerKind::None | PartnerKind::Called => {}
+        }
+    }
+
+    /// Sets deal_order and deal_pauses from GameOptions::deal.
+    fn plan_deal(&mut self) {
+        let pattern = &self.options.deal;
+        let n = self.player_count;
+        let hand_size = self.options.hand_size;
+        let nest_size = self.options.nest_size as usize;
+
+        let mut order = Vec::new();
+        let mut round_ends = Vec::new();
+        let mut nest_dealt = 0;
+        if pattern.nest == NestDeal::First {
+            order.extend(vec![None; nest_size]);
+            nest_dealt = nest_size;
+        }
+        let mut dealt = vec![0; n];
+        let mut round = 0;
+        while dealt.iter().any(|count| *count < hand_size) {
+            let packet_size = pattern.packet_sizes[round % pattern.packet_sizes.len()];
+            for i in 1..=n {
+                let p = (self.dealer + i) % n;
+                let count = packet_size.min(hand_size - dealt[p]);
+                order.extend(vec![Some(p); count as usize]);
+                dealt[p] += count;
+            }
+            if pattern.nest == NestDeal::Interleaved && nest_dealt < nest_size {
+                order.push(None);
+                nest_dealt += 1;
+            }
+            round_ends.push(order.len());
+            round += 1;
+        }
+        order.extend(vec![None; nest_size - nest_dealt]);
+
+        // The last pause is always the end of the deal.
+        let mut pauses = match pattern.bidding {
+            BidTiming::DuringDeal(rounds) => {
+                let skip = round_ends.len().saturating_sub(rounds as usize);
+                round_ends[skip..round_ends.len() - 1].to_vec()
+            }
+            BidTiming::AfterDeal => Vec::new(),
+        };
+        pauses.push(order.len());
+
+        self.deal_order = order;
+        self.deal_pauses = pauses;
+    }
 
-        self.assign_across_partners();
+    fn next_deal_action(&self) -> GameAction {
+        match self.deal_order[self.deal_count] {
+            Some(p) => DealCard(p, Vec::new()),
+            None => DealToNest,
+        }
     }
 
-    /// Deals a single card to active_player. Flips it face up and sorts
-    /// the hand if the player is human.
-    pub fn deal_card(&mut self) {
-        let p = self.active_player;
+    /// Deals a single card to player p. Flips it face up and sorts the hand
+    /// if the player is human, unless they might bid blind nil.
+    pub fn deal_card(&mut self, p: PlayerId) {
         if let Some(id) = self.deck.pop() {
             self.players[p].add_to_hand(id);
-            if !self.active_player_is_bot() {
+            if !self.player_is_bot(p) {
+                let face_up = !self.blind_nil_allowed();
                 if let Some(card) = self.cards.get_mut(id) {
-                    card.face_up = true;
+                    card.face_up = face_up;
                 }
                 self.sort_hand(p);
             }
@@ -258,7 +516,8 @@ impl Game {
         for id in &self.players[p].hand {
             sorted_cards.push(self.cards.get(*id).unwrap());
         }
-        sorted_cards.sort();
+        // Copies sit together, first copy first.
+        sorted_cards.sort_by(|a, b| a.cmp(b).then(a.copy.cmp(&b.copy)));
 
         // Reassign the hand ids to match the sorted hand_cards ids.
         self.players[p].hand.clear();
@@ -275,37 +534,171 @@ impl Game {
     //     }
     // }
 
-    pub fn make_bid(&mut self, bid: Option<CardSuit>) {
-        match bid {
-            Some(suit) => {
-                self.maker = Some(self.active_player);
-                self.set_trump(suit);
+    /// Records the active player's bid or pass. If the auction closes, the high
+    /// bidder becomes the maker. Otherwise the turn moves to the next player
+    /// still in the auction.
+    pub fn make_bid(&mut self, points: Option<Points>) {
+        let bid = Bid {
+            player: self.active_player,
+            points,
+        };
+        println!("P:{} bids {:?}", bid.player, bid.points);
+        self.auction.record(bid);
+        self.bid_turns_left = self.bid_turns_left.saturating_sub(1);
+
+        if self.auction.closed(&self.options) {
+            if let Some(high_bid) = self.auction.high_bid {
+                self.maker = Some(high_bid.player);
+                self.assign_makers_and_defenders();
             }
+        } else {
+            self.advance_to_next_bidder();
+        }
+    }
+
+    fn advance_to_next_bidder(&mut self) {
+        self.advance_active_player();
+        while !self.auction.in_auction(self.active_player) {
+            self.advance_active_player();
+        }
+    }
+
+    pub fn blind_nil_allowed(&self) -> bool {
+        self.has_phase(Phase::TrickBidding) && self.options.spades.blind_nil
+    }
+
+    /// The bids the active player may make with Phase::TrickBidding. Blind
+    /// nil is only open before they've looked at their cards.
+    pub fn legal_trick_bids(&self) -> Vec<TrickBid> {
+        let mut bids = Vec::new();
+        if self.blind_nil_allowed() && !self.looked[self.active_player] {
+            bids.push(TrickBid::BlindNil);
+        }
+        if self.options.spades.nil {
+            bids.push(TrickBid::Nil);
+        }
+        for tricks in 1..=self.options.hand_size {
+            bids.push(TrickBid::Tricks(tricks));
+        }
+        bids
+    }
+
+    /// Records the active player's bid. A blind bidder picks up their cards
+    /// once they've bid. Once everyone has bid the phase ends.
+    pub fn bid_tricks(&mut self, bid: TrickBid) {
+        println!("P:{} bids {}", self.active_player, bid.name());
+        self.trick_bids[self.active_player] = Some(bid);
+        if !self.looked[self.active_player] {
+            self.look_at_hand();
+        }
+        if self.trick_bids.iter().all(|bid| bid.is_some()) {
+            self.next_action = Some(self.next_phase(Phase::TrickBidding));
+        } else {
+            self.advance_active_player();
+            self.next_action = Some(WaitForTrickBid);
+        }
+    }
+
+    /// Turns the active player's cards face up. A bot's stay face down.
+    pub fn look_at_hand(&mut self) {
+        self.looked[self.active_player] = true;
+        let face_up = !self.active_player_is_bot();
+        for id in self.active_hand().clone() {
+            self.cards[id].face_up = face_up;
+        }
+    }
+
+    /// The nest card turned up for Phase::TrumpBidding.
+    pub fn turn_up(&self) -> Option<CardId> {
+        self.nest.last().copied()
+    }
+
+    /// In the first round of trump bidding a bid orders up the turned card.
+    pub fn ordering_up(&self) -> bool {
+        self.trump_passes < self.player_count
+    }
+
+    /// The bids the active player may make with Phase::TrumpBidding. None is
+    /// a pass. With AllPassPolicy::StickTheDealer the dealer can't pass the
+    /// last turn of the second round.
+    pub fn legal_trump_bids(&self) -> Vec<Option<CardSuit>> {
+        let turned = self.cards[self.turn_up().unwrap()].suit;
+        let mut bids = Vec::new();
+        let last_turn = self.trump_passes + 1 == 2 * self.player_count;
+        if !last_turn || self.options.all_pass != AllPassPolicy::StickTheDealer {
+            bids.push(None);
+        }
+        if self.ordering_up() {
+            bids.push(Some(turned));
+        } else {
+            let suits = [
+                CardSuit::Club,
+                CardSuit::Diamond,
+                CardSuit::Heart,
+                CardSuit::Spade,
+            ];
+            bids.extend(suits.into_iter().filter(|suit| *suit != turned).map(Some));
+        }
+        bids
+    }
+
+    /// Records the active player's trump bid. Ordering up has the dealer pick
+    /// up the turned card. A suit named in the second round skips the
+    /// exchange. After two rounds of passes the hand is thrown in.
+    pub fn bid_trump(&mut self, suit: Option<CardSuit>) {
+        let suit = match suit {
+            Some(suit) => suit,
             None => {
-                self.pass_count += 1;
-                println!("pass count: {}", self.pass_count);
+                println!("P:{} passes trump", self.active_player);
+                self.trump_passes += 1;
+                if self.trump_passes == 2 * self.player_count {
+                    self.next_action = Some(ThrowIn);
+                    return;
+                }
+                if self.trump_passes == self.player_count {
+                    // Turn the card down for the second round.
+                    let id = self.turn_up().unwrap();
+                    self.cards[id].face_up = false;
+                }
                 self.advance_active_player();
+                self.next_action = Some(WaitForTrumpBid);
+                return;
             }
+        };
+
+        println!("P:{} makes {} trump", self.active_player, suit.to_string());
+        self.maker = Some(self.active_player);
+        self.assign_makers_and_defenders();
+        self.set_trump(Some(suit));
+        self.next_action = match self.ordering_up() {
+            true => Some(self.next_phase(Phase::TrumpBidding)),
+            false => Some(self.next_phase(Phase::NestExchange)),
+        };
+    }
+
+    /// The winning bid, once the auction has closed.
+    pub fn contract(&self) -> Option<Points> {
+        match self.maker {
+            Some(_) => self.auction.high_bid.and_then(|bid| bid.points),
+            None => None,
         }
     }
 
+    /// With a called partner, everyone but the maker is Unknown until the
+    /// partner is revealed.
     pub fn assign_makers_and_defenders(&mut self) {
         let maker = self.maker.unwrap();
-        let maker_partner = self.players[maker].partner;
+        let maker_partners = self.players[maker].partners.clone();
+        let hidden = self.options.partner_kind == PartnerKind::Called && !self.partner_revealed;
 
         for (id, player) in self.players.iter_mut().enumerate() {
-            if id == maker {
+            if id == maker || maker_partners.contains(&id) {
                 player.kind = Some(PlayerKind::Maker);
+            } else if hidden {
+                player.kind = Some(PlayerKind::Unknown);
             } else {
                 player.kind = Some(PlayerKind::Defender);
             }
-            if let Some(maker_partner) = maker_partner {
-                if id == maker_partner {
-                    player.kind = Some(PlayerKind::Maker);
-                } else {
-                    player.kind = Some(PlayerKind::Defender);
-                }
-            }
         }
     }
 
@@ -317,73 +710,205 @@ impl Game {
         cards
     }
 
+    /// The maker picks up the nest. With Phase::TrumpBidding the dealer picks
+    /// up the turned card instead.
     pub fn move_nest_card_to_hand(&mut self) {
-        let p = self.maker.unwrap();
+        let p = match self.has_phase(Phase::TrumpBidding) {
+            true => self.dealer,
+            false => self.maker.unwrap(),
+        };
+        self.active_player = p;
+        let face_up = !self.player_is_bot(p);
         for _ in 0..self.options.nest_size {
             if let Some(id) = self.nest.pop() {
-                self.cards.get_mut(id).unwrap().face_up = true;
+                self.cards.get_mut(id).unwrap().face_up = face_up;
                 self.players[p].hand.push(id);
             }
         }
         self.sort_hand(p);
     }
 
+    /// The special cards never go to the nest. With DiscardRules::no_counters
+    /// neither do point cards, unless there aren't enough others to fill it.
     pub fn eligible_discards(&self) -> Vec<CardId> {
         let mut ids = Vec::new();
         for id in &self.active_player().hand {
             if let Some(card) = self.cards.get(*id) {
-                if card.suit == CardSuit::Joker {
+                if card.suit.is_special() {
                     continue;
                 }
                 ids.push(*id);
             }
         }
+        if self.options.discard_rules.no_counters && !self.passing {
+            let needed = self.exchange_size().saturating_sub(self.nest.len());
+            let blanks: Vec<CardId> = ids
+                .iter()
+                .filter(|id| self.cards[**id].points == 0)
+                .copied()
+                .collect();
+            if blanks.len() >= needed {
+                return blanks;
+            }
+        }
         ids
     }
 
+    /// The suits the maker may name. With DiscardRules::no_trump a suit
+    /// buried in the nest is out, unless every suit is.
+    pub fn trump_choices(&self) -> Vec<CardSuit> {
+        let suits = vec![
+            CardSuit::Club,
+            CardSuit::Diamond,
+            CardSuit::Heart,
+            CardSuit::Spade,
+        ];
+        if !self.options.discard_rules.no_trump {
+            return suits;
+        }
+        let choices: Vec<CardSuit> = suits
+            .iter()
+            .filter(|suit| !self.buried.iter().any(|id| self.cards[*id].suit == **suit))
+            .copied()
+            .collect();
+        match choices.is_empty() {
+            true => suits,
+            false => choices,
+        }
+    }
+
+    /// A human maker's discards stay face up until the exchange ends so they
+    /// can be taken back.
     pub fn discard_to_nest(&mut self, discards: &[CardId]) {
         self.mark_select_state(discards, SelectState::Unselectable);
+        let face_up = !self.active_player_is_bot();
         for id in discards {
             self.active_player_mut().remove_from_hand(id);
             self.nest.push(*id);
             if let Some(card) = self.cards.get_mut(*id) {
-                card.face_up = false;
+                card.face_up = face_up;
             }
         }
     }
 
     pub fn undiscard_from_nest(&mut self, id: &CardId) {
-        let player_id = self.maker.unwrap();
+        let player_id = self.active_player;
         self.nest.retain(|i| i != id);
         let winner = &mut self.players[player_id];
         winner.add_to_hand(*id);
         self.sort_hand(player_id);
     }
 
-    /// Mark the cards matching trump.
-    pub fn set_trump(&mut self, suit: CardSuit) {
-        self.trump_suit = Some(suit);
+    /// The maker may finish the exchange once the nest is back to nest_size.
+    /// A passing player is done once they've put out the cards to pass.
+    pub fn nest_exchange_ready(&self) -> bool {
+        self.nest.len() == self.exchange_size()
+    }
+
+    /// How many cards go out in the exchange: the nest, or a pass.
+    pub fn exchange_size(&self) -> usize {
+        match self.has_phase(Phase::PassCards) {
+            true => self.options.hearts.pass_size as usize,
+            false => self.options.nest_size as usize,
+        }
+    }
+
+    pub fn pass_direction(&self) -> PassDirection {
+        PassDirection::for_hand(self.hands_played)
+    }
+
+    /// Starts the pass with the player left of the dealer. On a hold hand
+    /// play starts right away.
+    fn prepare_pass(&mut self) {
+        if self.pass_direction() == PassDirection::Hold {
+            self.next_action = Some(self.next_phase(Phase::PassCards));
+            return;
+        }
+        self.passing = true;
+        self.passes = vec![Vec::new(); self.player_count];
+        self.active_player = (self.dealer + 1) % self.player_count;
+        self.next_action = Some(WaitForDiscards);
+    }
+
+    /// Sets the active player's cards aside, face down, until everyone has
+    /// passed.
+    fn end_pass(&mut self) {
+        let ids = self.active_hand().clone();
+        self.mark_select_state(&ids, SelectState::Unselectable);
+        let passed: Vec<CardId> = self.nest.drain(..).collect();
+        for id in &passed {
+            if let Some(card) = self.cards.get_mut(*id) {
+                card.face_up = false;
+                card.select_state = SelectState::Unselectable;
+            }
+        }
+        self.passes[self.active_player] = passed;
+
+        if self.passes.iter().all(|passed| !passed.is_empty()) {
+            self.next_action = Some(ReceivePasses);
+        } else {
+            self.advance_active_player();
+            self.next_action = Some(WaitForDiscards);
+        }
+    }
+
+    /// Each player picks up the cards passed to them.
+    fn receive_passes(&mut self) {
+        let direction = self.pass_direction();
+        for p in 0..self.player_count {
+            let target = direction.target(p, self.player_count).unwrap();
+            let face_up = !self.player_is_bot(target);
+            for id in self.passes[p].clone() {
+                self.cards[id].face_up = face_up;
+                self.players[target].add_to_hand(id);
+            }
+            self.sort_hand(target);
+            println!("P{p} passes {} to P{target}", direction.name());
+        }
+        self.passing = false;
+    }
+
+    /// Hide the discards and add any cards left in the deck to the nest.
+    fn end_nest_exchange(&mut self) {
+        let ids = self.active_hand().clone();
+        self.mark_select_state(&ids, SelectState::Unselectable);
+        self.buried_points = self.nest_points();
+        self.buried = self.nest.clone();
+        self.nest.append(&mut self.deck);
+        for id in self.nest.clone() {
+            if let Some(card) = self.cards.get_mut(id) {
+                card.face_up = false;
+                card.select_state = SelectState::Unselectable;
+            }
+        }
+    }
+
+    /// Mark the cards the rules make trump, and rank them. With no trump suit
+    /// only the special cards are trump.
+    pub fn set_trump(&mut self, suit: Option<CardSuit>) {
+        self.trump_suit = suit;
+        let rules = self.rules();
         for card in self.cards.values_mut() {
-            if card.suit == suit || card.suit == CardSuit::Joker {
+            if card.suit.is_special() {
+                card.is_trump = true;
+            } else if let Some(rank) = suit.and_then(|suit| rules.trump_rank(card, suit)) {
                 card.is_trump = true;
+                card.game_rank = rank;
             }
         }
     }
 
+    /// With GameOptions::renege any card in hand may be played.
     pub fn get_playable_card_ids(&self) -> Vec<CardId> {
-        let mut ids = Vec::new();
-        let card_count_matching_lead = self.card_count_matching_lead();
-
-        for id in self.active_hand() {
-            let card = self.cards.get(*id).unwrap();
-            //println!("checking id: {:?}", id);
-            if self
-                .trick
-                .is_eligible(card, card_count_matching_lead)
-            {
-                ids.push(*id);
-            }
+        match self.options.renege.allowed {
+            true => self.active_hand().clone(),
+            false => self.legal_card_ids(),
         }
+    }
+
+    /// The cards the rules of play allow, renege or not.
+    pub fn legal_card_ids(&self) -> Vec<CardId> {
+        let ids = self.rules().legal_card_ids(self);
         if ids.is_empty() {
             println!(
                 "No playable ids out of {} cards for p:{}",
@@ -391,7 +916,6 @@ impl Game {
                 self.active_player
             );
             println!("Lead card: {:?}", self.trick.lead_card);
-            println!("# matching lead: {card_count_matching_lead}");
             for id in self.active_hand() {
                 let card = self.cards.get(*id).unwrap();
                 println!("Hand card: {:?}", card);
@@ -402,12 +926,12 @@ impl Game {
         ids
     }
 
-    fn card_count_matching_lead(&self) -> usize {
+    pub fn card_count_matching_lead(&self) -> usize {
         let mut count = 0;
         if let Some(lead_card) = &self.trick.lead_card {
             for id in self.active_hand() {
                 let card = self.cards.get(*id).unwrap();
-                if card.suit == lead_card.suit {
+                if card.follows(lead_card) {
                     count += 1;
                 }
             }
@@ -415,7 +939,23 @@ impl Game {
         count
     }
 
-    pub fn play_card_id(&mut self, id: &CardId) {
+    pub fn play_card_id(&mut self, id: &CardId) -> Result<(), GameError> {
+        if !self.cards.contains_key(*id) {
+            return Err(GameError::UnknownCard(*id));
+        }
+        if !self.active_hand().contains(id) {
+            return Err(GameError::CardNotInHand(*id));
+        }
+
+        if self.options.renege.allowed && !self.legal_card_ids().contains(id) {
+            println!("P{} revokes", self.active_player);
+            self.revokes.push(Revoke {
+                player: self.active_player,
+                card: *id,
+                trick: self.tricks_played,
+            });
+        }
+
         // Turn off selectability for all cards in hand.
         for id in self.active_hand().clone() {
             if let Some(card) = self.cards.get_mut(id) {
@@ -424,8 +964,54 @@ impl Game {
         }
 
         self.active_player_mut().remove_from_hand(id);
-        let card = self.cards.get_mut(*id).unwrap();
-        self.trick.add_card(self.active_player, card);
+        let takes_lead = self.rules().takes_lead(&self.trick, &self.cards[*id]);
+        let card = &mut self.cards[*id];
+        self.trick.add_card(self.active_player, card, takes_lead);
+        if card.is_trump {
+            self.trump_broken = true;
+        }
+
+        if self.called_card == Some(*id) {
+            self.reveal_called_partner(Some(self.active_player));
+        }
+
+        if !self.joker_played && self.cards[*id].suit == CardSuit::Joker {
+            self.update_later_jokers(*id);
+        }
+        Ok(())
+    }
+
+    /// Restore the Jokers to the rank and points given in the deck options.
+    fn reset_jokers(&mut self) {
+        self.joker_played = false;
+        let specials = &self.options.deck.special_cards;
+        if let Some(joker) = specials.iter().find(|s| s.suit == CardSuit::Joker) {
+            for card in self.cards.values_mut() {
+                if card.suit == CardSuit::Joker {
+                    card.game_rank = joker.rank as GameRank;
+                    card.points = joker.points;
+                }
+            }
+        }
+    }
+
+    /// After the first Joker is played, the others take the later_joker rank and points.
+    fn update_later_jokers(&mut self, first_id: CardId) {
+        self.joker_played = true;
+        let later_joker = match &self.options.later_joker {
+            Some(later_joker) => later_joker.clone(),
+            None => return,
+        };
+        for card in self.cards.values_mut() {
+            if card.suit == CardSuit::Joker && card.id != first_id {
+                card.game_rank = later_joker.game_rank;
+                card.points = later_joker.points;
+            }
+        }
+        // The holder's hand order may have changed.
+        for p in 0..self.player_count {
+            self.sort_hand(p);
+        }
     }
 
     pub fn trick_completed(&self) -> bool {
@@ -437,16 +1023,50 @@ impl Game {
         self.players[winner].add_to_tricks(self.trick.clone());
         self.last_trick_winner = winner;
         self.tricks_played += 1;
+
+        // A called card left in the nest never gets played.
+        if self.hand_completed() && self.called_card.is_some() && !self.partner_revealed {
+            self.reveal_called_partner(None);
+        }
     }
 
     pub fn prepare_for_new_trick(&mut self) {
+        if self.tricks_played == 0 {
+            self.active_player = self.rules().first_leader(self);
+        }
         if let Some(trick_winner) = self.trick.winner {
             self.active_player = trick_winner;
         }
         if !self.active_player().active {
             self.advance_active_player();
         }
-        self.trick = Trick::new(self.player_count);
+        self.trick = Trick::short_handed(self.player_count, &self.sitting_out());
+        self.trick.number = self.tricks_played;
+        self.trick.duplicate_winner = self.options.duplicate_winner;
+    }
+
+    pub fn sitting_out(&self) -> Vec<PlayerId> {
+        (0..self.player_count)
+            .filter(|p| !self.players[*p].active)
+            .collect()
+    }
+
+    /// The maker's partners sit out the hand. Their cards stay in hand, face
+    /// down and dimmed.
+    pub fn go_alone(&mut self) {
+        self.alone = true;
+        let maker = self.maker.unwrap();
+        for p in self.players[maker].partners.clone() {
+            self.players[p].active = false;
+            let hand = self.players[p].hand.clone();
+            self.mark_select_state(&hand, SelectState::Dimmed);
+            for id in hand {
+                if let Some(card) = self.cards.get_mut(id) {
+                    card.face_up = false;
+                }
+            }
+            println!("P{p} sits out the hand");
+        }
     }
 
     pub fn hand_completed(&self) -> bool {
@@ -455,67 +1075,256 @@ impl Game {
 
     pub fn nest_points(&self) -> Points {
         let mut points = 0;
-        for id in &self.deck {
+        for id in &self.nest {
             let card = self.cards.get(*id).unwrap();
             points += card.points;
         }
         points
     }
 
-    fn award_nest(&mut self) {
-        let pts = self.nest_points() + self.options.nest_points_bonus;
-        for (id, player) in self.players.iter_mut().enumerate() {
-            if id == self.last_trick_winner {
-                player.points_this_hand += pts;
-                println!("Nest points awarded : {pts}");
+    /// Score the hand by the rules and add each player's score.
+    pub fn end_hand(&mut self) {
+        let trick_points = self.players.iter().map(|p| p.points_this_hand).collect();
+        let mut breakdowns = self.rules().score_hand(self);
+        self.add_renege_penalties(&mut breakdowns);
+        let scores: Vec<Points> = breakdowns.iter().map(|b| b.total()).collect();
+        for (player, score) in self.players.iter_mut().zip(&scores) {
+            let total = player.finalize_score(*score);
+            println!("Hand score: {score}, total: {total}");
+        }
+        self.record_scoresheet_rows();
+        self.hands_played += 1;
+        self.last_hand = Some(HandResult {
+            trick_points,
+            nest_points: self.players.iter().map(|p| p.nest_points).collect(),
+            breakdowns,
+            scores,
+        });
+        self.check_for_winners();
+    }
+
+    /// Adds this hand to each team's scoresheet. Call after finalizing scores.
+    fn record_scoresheet_rows(&mut self) {
+        // Without an auction, as in Euchre, the makers need most of the tricks.
+        let made = match (self.maker, self.contract()) {
+            (Some(_), Some(_)) => self.hand_summary().made(),
+            (Some(maker), None) => {
+                self.side_take(maker).tricks * 2 > self.options.hand_size as usize
             }
+            (None, _) => false,
+        };
+        let bidder = self.maker;
+        let bid = self.contract();
+        for team in &mut self.teams {
+            let members = || team.players.iter().map(|p| &self.players[*p]);
+            // Everyone on a team scores the same, so any member's score will do.
+            let first = &self.players[team.players[0]];
+            let score = first.score - team.score();
+            let row = ScoresheetRow {
+                dealer: self.dealer,
+                bidder,
+                bid,
+                trump: self.trump_suit,
+                points_taken: members().map(|p| p.points_this_hand).sum(),
+                nest_points: members().map(|p| p.nest_points).sum(),
+                made,
+                score,
+                total: first.score,
+            };
+            team.scoresheet.push(row);
         }
     }
 
-    fn makers_and_defenders_points(&self) -> (Points, Points) {
-        let mut makers_pts = 0;
-        let mut defenders_pts = 0;
-        for p in &self.players {
-            match &p.kind {
-                Some(kind) => match kind {
-                    PlayerKind::Maker => makers_pts += p.points_this_hand,
-                    PlayerKind::Defender => defenders_pts += p.points_this_hand,
-                },
-                None => {}
+    /// What player p and their partners took this hand. Against a called
+    /// partnership, all the defenders are one side.
+    pub fn side_take(&self, p: PlayerId) -> SideTake {
+        if self.options.partner_kind == PartnerKind::Called
+            && self.players[p].kind == Some(PlayerKind::Defender)
+        {
+            return self.kind_take(PlayerKind::Defender);
+        }
+        let mut ids = self.players[p].partners.clone();
+        ids.push(p);
+        self.take(&ids)
+    }
+
+    /// Records a call of a renege. It's correct if the accused revoked at any
+    /// point this hand.
+    pub fn call_renege(&mut self, caller: PlayerId, accused: PlayerId) {
+        let correct = self.revokes.iter().any(|r| r.player == accused);
+        println!("P{caller} calls a renege on P{accused}. Correct: {correct}");
+        self.renege_calls.push(RenegeCall {
+            caller,
+            accused,
+            correct,
+        });
+    }
+
+    /// A side caught reneging forfeits the hand and pays the penalty. Each
+    /// wrong call costs the caller's side.
+    fn add_renege_penalties(&self, breakdowns: &mut [ScoreBreakdown]) {
+        let rules = &self.options.renege;
+        for (p, breakdown) in breakdowns.iter_mut().enumerate() {
+            let caught = self
+                .renege_calls
+                .iter()
+                .any(|call| call.correct && self.same_side(p, call.accused));
+            if caught {
+                let total = breakdown.total();
+                breakdown
+                    .items
+                    .push(ScoreItem::Renege(-total - rules.penalty));
+            }
+            for call in &self.renege_calls {
+                if !call.correct && self.same_side(p, call.caller) {
+                    breakdown
+                        .items
+                        .push(ScoreItem::FalseRenegeCall(-rules.false_call_penalty));
+                }
+            }
+        }
+    }
+
+    /// Partners this hand count as one side.
+    fn same_side(&self, a: PlayerId, b: PlayerId) -> bool {
+        a == b || self.players[a].partners.contains(&b)
+    }
+
+    /// Sets game_over and winners if anyone has reached the winning score.
+    /// When the low score wins, reaching it ends the game for everyone. So
+    /// does falling to SpadesOptions::losing_score in Spades.
+    fn check_for_winners(&mut self) {
+        let mut candidates: Vec<PlayerId> = (0..self.player_count)
+            .filter(|p| self.players[*p].score >= self.options.winning_score)
+            .collect();
+        let losing_score = self.options.spades.losing_score;
+        if candidates.is_empty()
+            && self.has_phase(Phase::TrickBidding)
+            && self.players.iter().any(|p| p.score <= losing_score)
+        {
+            candidates = (0..self.player_count).collect();
+        }
+        if candidates.is_empty() {
+            return;
+        }
+        let low_score_wins = self.rules().low_score_wins();
+        if low_score_wins {
+            candidates = (0..self.player_count).collect();
+        }
+
+        if self.options.makers_go_out_first {
+            let makers: Vec<PlayerId> = candidates
+                .iter()
+                .copied()
+                .filter(|p| matches!(self.players[*p].kind, Some(PlayerKind::Maker)))
+                .collect();
+            if !makers.is_empty() {
+                candidates = makers;
             }
         }
-        (makers_pts, defenders_pts)
+
+        let scores = candidates.iter().map(|p| self.players[*p].score);
+        let top_score = match low_score_wins {
+            true => scores.min(),
+            false => scores.max(),
+        }
+        .unwrap();
+        let leaders: Vec<PlayerId> = candidates
+            .into_iter()
+            .filter(|p| self.players[*p].score == top_score)
+            .collect();
+
+        let tied = leaders
+            .iter()
+            .any(|a| leaders.iter().any(|b| !self.same_side(*a, *b)));
+        if tied && self.options.tie_break == TieBreak::PlayOn {
+            println!("Tied at {top_score}. Playing on.");
+            return;
+        }
+
+        self.winners = leaders;
+        self.game_over = true;
     }
 
-    pub fn makers_and_defenders_score(&self) -> (Points, Points) {
-        let (makers_pts, defenders_pts) = self.makers_and_defenders_points();
-        let points_needed = 70; ////////// read from GameOptions instead
+    /// The nest's card points times the multiplier, plus the bonus.
+    pub fn nest_value(&self) -> Points {
+        self.nest_points() * self.options.nest_points_multiplier + self.options.nest_points_bonus
+    }
 
-        let makers_score;
-        let defenders_score;
+    /// Who takes the nest, according to GameOptions::nest_owner.
+    fn nest_owner(&self) -> PlayerId {
+        let maker = self.maker.unwrap();
+        match self.options.nest_owner {
+            NestOwner::LastTrickWinner => self.last_trick_winner,
+            NestOwner::Maker => maker,
+            NestOwner::DefendersWhenSet => {
+                if self.kind_take(PlayerKind::Maker).points >= self.contract().unwrap() {
+                    return maker;
+                }
+                self.defender_taking_nest()
+            }
+        }
+    }
 
-        if makers_pts >= points_needed {
-            // Bid successful
-            makers_score = match self.options.makers_points_awarded_for_win {
-                PointsAwarded::Fixed(p) => p,
-                PointsAwarded::PointsTakenWithMultiplier(x) => makers_pts * x,
-            };
-            defenders_score = match self.options.makers_points_awarded_for_loss {
-                PointsAwarded::Fixed(p) => p,
-                PointsAwarded::PointsTakenWithMultiplier(x) => defenders_pts * x,
-            };
-        } else {
-            // Bid failed
-            makers_score = match self.options.makers_points_awarded_for_loss {
-                PointsAwarded::Fixed(p) => p,
-                PointsAwarded::PointsTakenWithMultiplier(x) => makers_pts * x,
-            };
-            defenders_score = match self.options.makers_points_awarded_for_win {
-                PointsAwarded::Fixed(p) => p,
-                PointsAwarded::PointsTakenWithMultiplier(x) => defenders_pts * x,
-            };
+    /// The last-trick winner if they're a defender, otherwise the first
+    /// defender left of the maker.
+    fn defender_taking_nest(&self) -> PlayerId {
+        let maker = self.maker.unwrap();
+        let is_defender = |p: PlayerId| self.players[p].kind == Some(PlayerKind::Defender);
+        if is_defender(self.last_trick_winner) {
+            return self.last_trick_winner;
+        }
+        (1..self.player_count)
+            .map(|i| (maker + i) % self.player_count)
+            .find(|p| is_defender(*p))
+            .unwrap_or(maker)
+    }
+
+    /// With DiscardRules::counters_to_defenders the buried card points go to
+    /// a defender and the owner keeps the rest of the nest's value.
+    pub fn award_nest(&mut self) {
+        let owner = self.nest_owner();
+        let mut pts = self.nest_value();
+        if self.options.discard_rules.counters_to_defenders && self.buried_points != 0 {
+            let buried = self.buried_points * self.options.nest_points_multiplier;
+            let defender = self.defender_taking_nest();
+            let player = &mut self.players[defender];
+            player.points_this_hand += buried;
+            player.nest_points += buried;
+            println!("Buried points awarded to P{defender}: {buried}");
+            pts -= buried;
+        }
+        let player = &mut self.players[owner];
+        player.points_this_hand += pts;
+        player.nest_points += pts;
+        println!("Nest points awarded to P{owner}: {pts}");
+    }
+
+    /// What all the players of one kind took this hand.
+    fn kind_take(&self, kind: PlayerKind) -> SideTake {
+        let ids: Vec<PlayerId> = (0..self.player_count)
+            .filter(|p| self.players[*p].kind == Some(kind))
+            .collect();
+        self.take(&ids)
+    }
+
+    fn take(&self, ids: &[PlayerId]) -> SideTake {
+        let mut take = SideTake::default();
+        for p in ids {
+            take.points += self.players[*p].points_this_hand;
+            take.tricks += self.players[*p].tricks.len();
+        }
+        take
+    }
+
+    pub fn hand_summary(&self) -> HandSummary {
+        let everyone: Vec<PlayerId> = (0..self.player_count).collect();
+        HandSummary {
+            contract: self.contract().unwrap(),
+            makers: self.kind_take(PlayerKind::Maker),
+            total: self.take(&everyone),
+            alone: self.alone,
         }
-        (makers_score, defenders_score)
     }
 
     fn mark_select_state(&mut self, card_ids: &[CardId], state: SelectState) {
@@ -528,39 +1337,55 @@ impl Game {
 
     /// After dealing or after a bid received, determine the next action.
     fn set_deal_or_bid_action(&mut self, dealing: bool) {
-        // Default action
-        self.next_action = Some(DealCard(0, Vec::new()));
-
         if dealing {
             // Have we reached a deal pause?
-            if self.deal_count == DEAL_PAUSES[self.deal_pause_idx] {
+            if self.deal_pauses.get(self.deal_pause_idx) == Some(&self.deal_count) {
                 self.deal_pause_idx += 1;
-                if self.deal_pause_idx == DEAL_PAUSES.len() {
-                    self.dealing_completed = true;
-                    if self.maker.is_some() {
-                        self.next_action = Some(MoveNestToHand);
-                    }
-                } else if self.maker.is_none() {
-                    self.next_action = Some(WaitForBid);
+                self.dealing_completed = self.deal_count == self.deal_order.len();
+                if self.maker.is_none() && self.has_phase(Phase::Bidding) {
+                    self.open_bidding();
+                    return;
                 }
             }
-        } else {
+        } else if self.maker.is_none() {
             // bidding
-            match &self.maker {
-                Some(_) => {
-                    if self.dealing_completed {
-                        self.next_action = Some(MoveNestToHand);
-                    }
-                }
-                None => {
-                    if self.pass_count == self.player_count as u8 {
-                        self.pass_count = 0;
-                    } else {
-                        self.next_action = Some(WaitForBid);
-                    }
-                }
-            };
+            if self.auction.closed(&self.options) {
+                self.next_action = Some(self.all_pass_action());
+                return;
+            } else if self.dealing_completed || self.bid_turns_left > 0 {
+                self.next_action = Some(WaitForBid);
+                return;
+            }
+        }
+
+        let dealt = match self.has_phase(Phase::Bidding) {
+            true => Phase::Bidding,
+            false => Phase::Deal,
+        };
+        self.next_action = match self.dealing_completed {
+            true => Some(self.next_phase(dealt)),
+            false => Some(self.next_deal_action()),
+        };
+    }
+
+    /// The action to take when everyone has passed.
+    fn all_pass_action(&self) -> GameAction {
+        match self.options.all_pass {
+            AllPassPolicy::Redeal => ThrowIn,
+            AllPassPolicy::StickTheDealer => StickDealer,
+            AllPassPolicy::ReopenLower(_) if self.auction.reopened => ThrowIn,
+            AllPassPolicy::ReopenLower(minimum) => ReopenBidding(minimum),
+        }
+    }
+
+    /// Give each player still in the auction one turn to bid before dealing resumes.
+    /// Once dealing is completed, bidding continues until the auction closes.
+    fn open_bidding(&mut self) {
+        if !self.auction.in_auction(self.active_player) {
+            self.advance_to_next_bidder();
         }
+        self.bid_turns_left = self.player_count - self.auction.pass_count();
+        self.next_action = Some(WaitForBid);
     }
 
     pub fn do_next_action(&mut self) {
@@ -577,60 +1402,113 @@ impl Game {
                 }
                 DealToNest => {
                     println!("game: DealToNest");
-                    // Remaining cards to nest
-                    for _ in 0..self.options.nest_size {
-                        if let Some(id) = self.deck.pop() {
-                            self.nest.push(id);
-                        }
+                    if let Some(id) = self.deck.pop() {
+                        self.nest.push(id);
                     }
-                    // Flip nest cards
-                    for i in 0..self.options.nest_face_up {
-                        let idx = self.nest.len() - 1 - i as usize;
-                        if let Some(card) = self.cards.get_mut(self.nest[idx]) {
-                            card.face_up = true;
+                    // Flip nest cards once the nest is dealt.
+                    if self.nest.len() == self.options.nest_size as usize {
+                        for i in 0..self.options.nest_face_up {
+                            let idx = self.nest.len() - 1 - i as usize;
+                            if let Some(card) = self.cards.get_mut(self.nest[idx]) {
+                                card.face_up = true;
+                            }
                         }
                     }
-                    self.next_action = Some(DealCard(self.active_player, Vec::new()));
+                    self.deal_count += 1;
+                    self.set_deal_or_bid_action(true);
                 }
-                DealCard(_, _) => {
-                    self.deal_card();
+                DealCard(p, _) => {
+                    self.deal_card(p);
                     self.deal_count += 1;
 
                     // Update the action with the new hand (after the card was dealt), since this
                     // will be sent to the Controller.
-                    action = DealCard(self.active_player, self.active_hand().clone());
-                    self.advance_active_player();
+                    action = DealCard(p, self.players[p].hand.clone());
                     self.set_deal_or_bid_action(true);
                 }
                 WaitForBid => {
                     println!("game: WaitForBid");
                 }
+                WaitForTrickBid => {
+                    println!("game: WaitForTrickBid");
+                }
+                WaitForTrumpBid => {
+                    println!("game: WaitForTrumpBid");
+                }
+                ThrowIn => {
+                    println!("game: ThrowIn");
+                    self.next_action = Some(PrepareForNewHand);
+                }
+                StickDealer => {
+                    println!("game: StickDealer");
+                    self.active_player = self.dealer;
+                    self.make_bid(Some(self.auction.minimum));
+                    self.set_deal_or_bid_action(false);
+                }
+                ReopenBidding(minimum) => {
+                    println!("game: ReopenBidding at {minimum}");
+                    self.auction = Auction::new(self.player_count, minimum);
+                    self.auction.reopened = true;
+                    self.open_bidding();
+                }
                 MoveNestToHand => {
                     println!("game: MoveNestToHand");
                     self.move_nest_card_to_hand();
                     self.next_action = Some(WaitForDiscards);
                 }
+                PreparePass => {
+                    println!("game: PreparePass {}", self.pass_direction().name());
+                    self.prepare_pass();
+                }
                 WaitForDiscards => {
                     println!("game::WaitForDiscards");
-                    for id in self.eligible_discards() {
-                        if let Some(card) = self.cards.get_mut(id) {
-                            card.select_state = SelectState::Selectable
+                    // Hand cards can go to the nest until it's full. Nest cards
+                    // can always come back.
+                    // A human sees the cards the discard rules hold back dimmed.
+                    let hand = self.active_hand().clone();
+                    self.mark_select_state(&hand, SelectState::Unselectable);
+                    if !self.nest_exchange_ready() {
+                        if !self.active_player_is_bot() {
+                            self.mark_select_state(&hand, SelectState::Dimmed);
                         }
+                        let ids = self.eligible_discards();
+                        self.mark_select_state(&ids, SelectState::Selectable);
                     }
+                    let nest = self.nest.clone();
+                    self.mark_select_state(&nest, SelectState::Selectable);
                 }
                 MoveCardToDiscard(id) => {
                     self.discard_to_nest(&vec![id]);
-                    if self.nest.len() == self.options.nest_size as usize {
-                        self.next_action = Some(PauseAfterDiscard);
-                    }
+                    self.next_action = Some(WaitForDiscards);
+                }
+                ReturnDiscardToHand(id) => {
+                    self.undiscard_from_nest(&id);
+                    self.next_action = Some(WaitForDiscards);
                 }
                 PauseAfterDiscard => {
                     self.next_action = Some(EndNestExchange);
                 }
-                EndNestExchange => {
-                    let ids = self.active_hand().clone();
-                    self.mark_select_state(&ids, SelectState::Unselectable);
-                    self.next_action = Some(PrepareForNewTrick)
+                EndNestExchange => match self.passing {
+                    true => self.end_pass(),
+                    false => {
+                        self.end_nest_exchange();
+                        self.next_action = Some(self.next_phase(Phase::NestExchange))
+                    }
+                },
+                ReceivePasses => {
+                    self.receive_passes();
+                    self.next_action = Some(self.next_phase(Phase::PassCards));
+                }
+                WaitForChooseTrump => {
+                    println!("game: WaitForChooseTrump");
+                }
+                WaitForCallPartner => {
+                    println!("game: WaitForCallPartner");
+                }
+                WaitForGoAlone => {
+                    // The dealer may have been active, picking up the turned card.
+                    self.active_player = self.maker.unwrap();
+                    println!("game: WaitForGoAlone");
                 }
                 PrepareForNewTrick => {
                     self.prepare_for_new_trick();
@@ -659,13 +1537,28 @@ impl Game {
                 AwardTrick(_) => {
                     println!("game: AwardTrick");
                     self.award_trick();
-                    self.next_action = Some(PrepareForNewTrick);
+                    if self.hand_completed() {
+                        self.next_action = Some(EndHand);
+                    } else {
+                        self.next_action = Some(PrepareForNewTrick);
+                    }
                 }
                 EndHand => {
-                    self.award_nest();
-                    println!("========= End of Hand ========")
+                    self.end_hand();
+                    println!("========= End of Hand ========");
+                    if self.game_over {
+                        self.next_action = Some(EndGame);
+                    } else {
+                        self.next_action = Some(PrepareForNewHand);
+                    }
+                }
+                EndGame => {
+                    println!("========= Game Over. Winners: {:?} ========", self.winners);
                 }
-                EndGame => todo!(),
+            }
+            if self.next_action.is_none() {
+                self.awaiting = Some(action.clone());
+                self.bot_seed = self.rng.u64(..);
             }
             self.actions_taken.push_back(action);
         }
@@ -674,33 +1567,187 @@ impl Game {
         }
     }
 
-    pub fn perform_player_action(&mut self, player_action: &PlayerAction) {
+    /// Checks player_action against the action the game is waiting at, the
+    /// active player and the cards they may use.
+    pub fn check_player_action(&self, player_action: &PlayerAction) -> Result<(), GameError> {
+        let wrong_phase = || GameError::WrongPhase {
+            action: *player_action,
+            awaiting: self.awaiting.clone().map(Box::new),
+        };
+        let check_card = |id: &CardId| match self.cards.contains_key(*id) {
+            true => Ok(()),
+            false => Err(GameError::UnknownCard(*id)),
+        };
+
+        match (player_action, &self.awaiting) {
+            (PlayerAction::DealCards, Some(PrepareForNewHand)) => Ok(()),
+            (PlayerAction::MakeBid(None), Some(WaitForBid)) => Ok(()),
+            (PlayerAction::MakeBid(Some(points)), Some(WaitForBid)) => {
+                match self.auction.is_legal(*points, &self.options) {
+                    true => Ok(()),
+                    false => Err(GameError::IllegalBid(*points)),
+                }
+            }
+            (PlayerAction::BidTricks(bid), Some(WaitForTrickBid)) => {
+                match self.legal_trick_bids().contains(bid) {
+                    true => Ok(()),
+                    false => Err(GameError::IllegalTrickBid(*bid)),
+                }
+            }
+            (PlayerAction::LookAtHand, Some(WaitForTrickBid)) => {
+                match self.looked[self.active_player] {
+                    true => Err(GameError::HandAlreadySeen),
+                    false => Ok(()),
+                }
+            }
+            (PlayerAction::BidTrump(suit), Some(WaitForTrumpBid)) => {
+                match (self.legal_trump_bids().contains(suit), suit) {
+                    (true, _) => Ok(()),
+                    (false, Some(suit)) => Err(GameError::IllegalTrump(*suit)),
+                    (false, None) => Err(GameError::DealerMustNameTrump),
+                }
+            }
+            (PlayerAction::ChooseTrump(suit), Some(WaitForChooseTrump)) => match suit {
+                Some(suit) if !self.trump_choices().contains(suit) => {
+                    Err(GameError::IllegalTrump(*suit))
+                }
+                None if !self.options.trump_rules.allow_no_trump => Err(GameError::NoTrumpNotAllowed),
+                _ => Ok(()),
+            },
+            (PlayerAction::CallPartner(id), Some(WaitForCallPartner)) => {
+                check_card(id)?;
+                match self.callable_card_ids().contains(id) {
+                    true => Ok(()),
+                    false => Err(GameError::CardNotCallable(*id)),
+                }
+            }
+            (PlayerAction::GoAlone(_), Some(WaitForGoAlone)) => Ok(()),
+            (PlayerAction::CallRenege(caller, accused), Some(WaitForPlayCard(_))) => {
+                if !self.options.renege.allowed {
+                    Err(GameError::RenegeNotAllowed)
+                } else if *caller >= self.player_count {
+                    Err(GameError::UnknownPlayer(*caller))
+                } else if *accused >= self.player_count {
+                    Err(GameError::UnknownPlayer(*accused))
+                } else if caller == accused {
+                    Err(GameError::SelfAccusation)
+                } else if self
+                    .renege_calls
+                    .iter()
+                    .any(|call| call.correct && call.accused == *accused)
+                {
+                    Err(GameError::AlreadyCaught(*accused))
+                } else {
+                    Ok(())
+                }
+            }
+            (PlayerAction::MoveCardToNest(id), Some(WaitForDiscards)) => {
+                check_card(id)?;
+                if self.nest_exchange_ready() {
+                    Err(GameError::NestFull)
+                } else if !self.active_hand().contains(id) {
+                    Err(GameError::CardNotInHand(*id))
+                } else if !self.eligible_discards().contains(id) {
+                    Err(GameError::IllegalDiscard(*id))
+                } else {
+                    Ok(())
+                }
+            }
+            (PlayerAction::TakeCardFromNest(id), Some(WaitForDiscards)) => {
+                check_card(id)?;
+                match self.nest.contains(id) {
+                    true => Ok(()),
+                    false => Err(GameError::CardNotInNest(*id)),
+                }
+            }
+            (PlayerAction::EndNestExchange, Some(WaitForDiscards)) => {
+                match self.nest_exchange_ready() {
+                    true => Ok(()),
+                    false => Err(GameError::NestNotFull),
+                }
+            }
+            (PlayerAction::PlayCard(p, id), Some(WaitForPlayCard(active_player))) => {
+                if p != active_player {
+                    return Err(GameError::NotYourTurn {
+                        player: *p,
+                        active_player: *active_player,
+                    });
+                }
+                check_card(id)?;
+                if !self.active_hand().contains(id) {
+                    Err(GameError::CardNotInHand(*id))
+                } else if !self.get_playable_card_ids().contains(id) {
+                    Err(GameError::CardNotPlayable(*id))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Err(wrong_phase()),
+        }
+    }
+
+    /// Applies a checked player_action. Call do_next_action afterwards to
+    /// continue the game. Rejected actions leave the game unchanged.
+    pub fn perform_player_action(&mut self, player_action: &PlayerAction) -> Result<(), GameError> {
+        self.check_player_action(player_action)?;
+        let awaiting = self.awaiting.take();
+
         match player_action {
             PlayerAction::DealCards => {
-                self.next_action = Some(DealToNest);
+                self.next_action = Some(self.next_deal_action());
             }
             PlayerAction::MakeBid(bid) => {
                 self.make_bid(*bid);
                 self.set_deal_or_bid_action(false);
             }
+            PlayerAction::BidTricks(bid) => {
+                self.bid_tricks(*bid);
+            }
+            PlayerAction::LookAtHand => {
+                self.look_at_hand();
+                self.next_action = Some(WaitForTrickBid);
+            }
+            PlayerAction::BidTrump(suit) => {
+                self.bid_trump(*suit);
+            }
+            PlayerAction::ChooseTrump(suit) => {
+                self.set_trump(*suit);
+                self.next_action = Some(self.next_phase(Phase::ChooseTrump));
+            }
+            PlayerAction::CallPartner(id) => {
+                self.call_partner(*id);
+                self.next_action = Some(self.next_phase(Phase::CallPartner));
+            }
+            PlayerAction::CallRenege(caller, accused) => {
+                // The game keeps waiting for the same play.
+                self.call_renege(*caller, *accused);
+                self.awaiting = awaiting;
+            }
+            PlayerAction::GoAlone(alone) => {
+                if *alone {
+                    self.go_alone();
+                }
+                self.next_action = Some(self.next_phase(Phase::GoAlone));
+            }
             PlayerAction::MoveCardToNest(id) => {
                 println!("MoveCardToNest");
                 self.next_action = Some(MoveCardToDiscard(*id));
             }
             PlayerAction::TakeCardFromNest(id) => {
-                println!("TakeCardFroNest");
-                self.undiscard_from_nest(id);
+                println!("TakeCardFromNest");
+                self.next_action = Some(ReturnDiscardToHand(*id));
             }
             PlayerAction::EndNestExchange => {
-                self.next_action = Some(EndNestExchange);
+                self.next_action = Some(PauseAfterDiscard);
             }
 
             PlayerAction::PlayCard(_p, c_id) => {
                 println!("game: PlayCard: {:?}", c_id);
-                self.play_card_id(c_id);
+                self.play_card_id(c_id)?;
                 self.next_action = Some(PauseAfterPlayCard);
             }
         }
+        Ok(())
     }
 }
 
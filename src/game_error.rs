@@ -0,0 +1,85 @@
+use std::fmt;
+
+use crate::bid::TrickBid;
+use crate::card::{CardId, CardSuit, Points};
+use crate::game::{GameAction, PlayerAction};
+use crate::player::PlayerId;
+
+/// Why Game::perform_player_action rejected a PlayerAction. The game is
+/// left unchanged when an action is rejected.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameError {
+    /// The game isn't waiting for this kind of action. awaiting is the
+    /// action the game is stopped at, if any. Boxed, as a GameAction can
+    /// hold a whole Trick.
+    WrongPhase {
+        action: PlayerAction,
+        awaiting: Option<Box<GameAction>>,
+    },
+    NotYourTurn {
+        player: PlayerId,
+        active_player: PlayerId,
+    },
+    UnknownCard(CardId),
+    CardNotInHand(CardId),
+    /// The card is in hand but breaks a rule of play, eg following suit.
+    CardNotPlayable(CardId),
+    IllegalBid(Points),
+    IllegalTrickBid(TrickBid),
+    /// The player has already looked at their cards this hand.
+    HandAlreadySeen,
+    IllegalTrump(CardSuit),
+    NoTrumpNotAllowed,
+    /// The dealer is stuck with the last turn of the trump bidding.
+    DealerMustNameTrump,
+    CardNotCallable(CardId),
+    IllegalDiscard(CardId),
+    CardNotInNest(CardId),
+    NestFull,
+    NestNotFull,
+    UnknownPlayer(PlayerId),
+    RenegeNotAllowed,
+    /// Players can't call a renege on themselves.
+    SelfAccusation,
+    /// The player was already caught reneging this hand.
+    AlreadyCaught(PlayerId),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::WrongPhase { action, awaiting } => match awaiting {
+                Some(awaiting) => write!(f, "{:?} not allowed while at {:?}", action, awaiting),
+                None => write!(f, "{:?} not allowed while the game is running", action),
+            },
+            GameError::NotYourTurn {
+                player,
+                active_player,
+            } => write!(
+                f,
+                "P{} played out of turn. It's P{}'s turn",
+                player, active_player
+            ),
+            GameError::UnknownCard(id) => write!(f, "No card with id {:?}", id),
+            GameError::CardNotInHand(id) => write!(f, "{:?} is not in the active hand", id),
+            GameError::CardNotPlayable(id) => write!(f, "{:?} can't be played to this trick", id),
+            GameError::IllegalBid(points) => write!(f, "A bid of {} is not allowed", points),
+            GameError::IllegalTrickBid(bid) => write!(f, "A bid of {} is not allowed", bid.name()),
+            GameError::HandAlreadySeen => write!(f, "The hand has already been seen"),
+            GameError::IllegalTrump(suit) => write!(f, "{:?} can't be trump", suit),
+            GameError::NoTrumpNotAllowed => write!(f, "No trump is not allowed"),
+            GameError::DealerMustNameTrump => write!(f, "The dealer must name trump"),
+            GameError::CardNotCallable(id) => write!(f, "{:?} can't be called", id),
+            GameError::IllegalDiscard(id) => write!(f, "{:?} can't be discarded", id),
+            GameError::CardNotInNest(id) => write!(f, "{:?} is not in the nest", id),
+            GameError::NestFull => write!(f, "The nest is full"),
+            GameError::NestNotFull => write!(f, "The nest isn't full yet"),
+            GameError::UnknownPlayer(p) => write!(f, "No player P{}", p),
+            GameError::RenegeNotAllowed => write!(f, "Reneging isn't allowed, so it can't be called"),
+            GameError::SelfAccusation => write!(f, "Players can't call a renege on themselves"),
+            GameError::AlreadyCaught(p) => write!(f, "P{} was already caught reneging this hand", p),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
@@ -1,13 +1,22 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{Read, Write};
+use std::path::Path;
 
-use crate::card::Points;
+use crate::card::{CardSuit, FaceRank, GameRank, Points};
+use crate::player::PlayerId;
+use crate::rules::RulesKind;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum PartnerKind {
     None,
+    /// Partner with the player sitting opposite. With 6 players this makes
+    /// three teams of two.
     Across,
+    /// Partner with every other player around the table. With 6 players this
+    /// makes two teams of three.
+    Alternate,
     Called,
 }
 
@@ -16,10 +25,253 @@ pub enum PointsAwarded {
     // assign separately to Makers and Defenders
     Fixed(Points),
     PointsTakenWithMultiplier(Points),
+    /// Score the contract.
+    Bid,
+    /// Lose the contract, eg for makers who are set.
+    MinusBid,
+}
+
+/// When the nest gets its cards during the deal.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NestDeal {
+    First,
+    Last,
+    /// One card after each round, any left over at the end.
+    Interleaved,
+}
+
+/// When bidding opens.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BidTiming {
+    /// Bidding pauses the deal after each of this many last rounds.
+    DuringDeal(u8),
+    AfterDeal,
+}
+
+/// How the cards are dealt. A round gives each player one packet, starting
+/// left of the dealer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DealPattern {
+    /// Cards in each round's packets, repeated until the hands are full.
+    pub packet_sizes: Vec<u8>,
+    pub nest: NestDeal,
+    pub bidding: BidTiming,
+}
+
+/// Who takes the nest's points at the end of the hand.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum NestOwner {
+    LastTrickWinner,
+    Maker,
+    /// The maker, unless the maker is set on trick points. Then the nest goes
+    /// to the defenders, to the last-trick winner if they're a defender.
+    DefendersWhenSet,
+}
+
+/// What happens when every player passes.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AllPassPolicy {
+    /// Throw the hand in and redeal. The deal passes to the left.
+    Redeal,
+    /// The dealer must take the bid at the minimum.
+    StickTheDealer,
+    /// Open the bidding again once at this lower minimum. If everyone
+    /// passes again, the hand is thrown in.
+    ReopenLower(Points),
+}
+
+/// What happens when more than one side reaches the winning score with
+/// the same total.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TieBreak {
+    /// Keep playing hands until the tie is broken.
+    PlayOn,
+    /// Everyone tied for the lead wins.
+    Shared,
+}
+
+/// Where the Rook Bird ranks among the trump.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BirdRank {
+    Highest,
+    Lowest,
+    TenAndAHalf,
+}
+
+impl BirdRank {
+    pub fn game_rank(&self) -> GameRank {
+        match self {
+            BirdRank::Highest => 20.0,
+            BirdRank::Lowest => 0.5,
+            BirdRank::TenAndAHalf => 10.5,
+        }
+    }
+}
+
+/// The rank and points a Joker takes when another Joker has already been
+/// played this hand.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LaterJokerOptions {
+    pub game_rank: GameRank,
+    pub points: Points,
+}
+
+/// Which of two identical cards played to a trick wins, in a deck with
+/// copies.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DuplicateWinner {
+    FirstPlayed,
+    LastPlayed,
+}
+
+/// Points for a single rank in a single suit. Overrides DeckOptions::rank_points.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SuitRankPoints {
+    pub suit: CardSuit,
+    pub rank: FaceRank,
+    pub points: Points,
+}
+
+/// Cards outside the four suits, Jokers or the Bird. The Bird's rank
+/// comes from GameOptions::bird_rank rather than from rank.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpecialCardOptions {
+    pub suit: CardSuit,
+    pub count: u8,
+    pub rank: FaceRank,
+    pub points: Points,
+}
+
+/// The cards in the deck. Each rank from low_rank to high_rank, less the removed
+/// ranks, is created in all four suits. The special cards are then added.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeckOptions {
+    pub low_rank: FaceRank,
+    pub high_rank: FaceRank,
+    pub removed_ranks: Vec<FaceRank>,
+    /// How many of each suited card, eg 2 for a double deck.
+    pub copies: u8,
+    /// Points for a rank in every suit. Ranks not listed are worth 0.
+    pub rank_points: BTreeMap<FaceRank, Points>,
+    pub suit_points: Vec<SuitRankPoints>,
+    pub special_cards: Vec<SpecialCardOptions>,
+}
+
+impl DeckOptions {
+    pub fn ranks(&self) -> Vec<FaceRank> {
+        (self.low_rank..=self.high_rank)
+            .filter(|rank| !self.removed_ranks.contains(rank))
+            .collect()
+    }
+
+    pub fn points_for(&self, suit: CardSuit, rank: FaceRank) -> Points {
+        for suit_points in &self.suit_points {
+            if suit_points.suit == suit && suit_points.rank == rank {
+                return suit_points.points;
+            }
+        }
+        *self.rank_points.get(&rank).unwrap_or(&0)
+    }
+}
+
+/// Variations on choosing and playing trump.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrumpRules {
+    /// The maker may choose no trump suit. The special cards are still trump.
+    pub allow_no_trump: bool,
+    /// A player who can't follow suit must play trump if they can.
+    pub must_trump_when_void: bool,
+    /// A player playing trump to a trick that trump is winning must beat it
+    /// if they can.
+    pub must_over_trump: bool,
+    /// Trump can't be led until a trump has been played, unless the leader
+    /// holds nothing else.
+    pub trump_must_be_broken: bool,
+}
+
+/// Renege: the table doesn't stop a player from failing to follow suit, but
+/// a side caught reneging forfeits the hand.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RenegeRules {
+    /// Any card in hand may be played. Revokes are recorded so players can
+    /// call them.
+    pub allowed: bool,
+    /// A side caught reneging scores minus this for the hand.
+    pub penalty: Points,
+    /// Taken from the side of a player who calls a renege that didn't happen.
+    pub false_call_penalty: Points,
+}
+
+/// Limits on what the maker may bury in the nest. A restriction is dropped
+/// when the hand doesn't have enough other cards to fill the nest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiscardRules {
+    /// Cards worth points can't be discarded.
+    pub no_counters: bool,
+    /// The maker can't name a suit they buried as trump. Trump is named after
+    /// the exchange, so this limits the choice of trump, not the discards.
+    pub no_trump: bool,
+    /// Points on the cards the maker buries score for the defenders, whoever
+    /// takes the nest.
+    pub counters_to_defenders: bool,
+}
+
+/// What happens when a player takes every point in a Hearts hand.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MoonShot {
+    /// Everyone else takes the points instead.
+    AddToOthers,
+    /// The shooter takes the points off their score.
+    SubtractFromShooter,
+}
+
+/// Options used only with RulesKind::Hearts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeartsOptions {
+    /// Cards each player passes before every hand but the hold hands.
+    pub pass_size: u8,
+    pub moon_shot: MoonShot,
+}
+
+/// Options used only with RulesKind::Spades. A partnership making its
+/// contract scores 10 a trick bid and 1 for each overtrick, or bag.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpadesOptions {
+    /// Players may bid to take no tricks.
+    pub nil: bool,
+    /// Won for a nil bid made, lost for one failed.
+    pub nil_bonus: Points,
+    /// Players may bid nil before seeing their cards. A human's cards are
+    /// dealt face down until they look.
+    pub blind_nil: bool,
+    pub blind_nil_bonus: Points,
+    /// Each time a partnership's bags add up to this many, they lose
+    /// bag_penalty and the count drops by bag_limit.
+    pub bag_limit: u8,
+    pub bag_penalty: Points,
+    /// A partnership falling to this score ends the game, and the highest
+    /// score wins.
+    pub losing_score: Points,
+}
+
+/// Options used only with RulesKind::Euchre. Makers taking most of the
+/// tricks score 1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EuchreOptions {
+    /// For makers taking every trick.
+    pub march: Points,
+    /// For a lone maker taking every trick.
+    pub alone_march: Points,
+    /// For defenders when the makers fail to take most of the tricks.
+    pub euchre: Points,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GameOptions {
+    /// The game being played. Each game uses the options that apply to it.
+    pub rules: RulesKind,
+    /// 3 to 6 players. See GameOptions::for_player_count.
+    pub player_count: PlayerId,
     pub hand_size: u8,
     /// This might be smaller than the number of cards left after dealing.
     /// If so, it becomes the effective exhange limit. Any remaining cards in
@@ -27,25 +279,333 @@ pub struct GameOptions {
     pub nest_size: u8,
     /// The number of nest cards presented face up.
     pub nest_face_up: u8,
+    pub deal: DealPattern,
     pub makers_points_awarded_for_win: PointsAwarded,
     pub makers_points_awarded_for_loss: PointsAwarded,
     pub defenders_points_awarded_for_win: PointsAwarded,
     pub defenders_points_awarded_for_loss: PointsAwarded,
+    /// Added for the side that takes every trick. 0 for none.
+    pub all_tricks_bonus: Points,
+    /// Added for the side that takes every point. 0 for none.
+    pub all_points_bonus: Points,
+    /// The most a side can score in one hand.
+    pub hand_score_cap: Option<Points>,
+    /// Defenders already at the winning score don't score on defense.
+    pub defenders_score_only_below_game: bool,
+    pub nest_owner: NestOwner,
+    /// The nest is worth its card points times this, plus nest_points_bonus.
+    pub nest_points_multiplier: Points,
     pub nest_points_bonus: i16,
+    pub discard_rules: DiscardRules,
+    /// None is cutthroat. Called means the maker names a card after choosing
+    /// trump and whoever holds it is the maker's secret partner.
+    pub partner_kind: PartnerKind,
+    /// With fixed partners, the maker may go alone once trump is named and
+    /// their partners sit out the hand. A made contract's score is multiplied
+    /// by this. None to not allow going alone. Euchre always allows it and
+    /// scores it by EuchreOptions.
+    pub alone_multiplier: Option<Points>,
+    /// The opening bid must be at least this much.
+    pub bid_minimum: Points,
+    /// Each bid must raise the previous one by a multiple of this.
+    pub bid_increment: Points,
+    pub bid_maximum: Points,
+    pub all_pass: AllPassPolicy,
+    pub trump_rules: TrumpRules,
+    pub renege: RenegeRules,
+    /// Hearts and Spades always play with a standard 52-card deck instead, and
+    /// Euchre with its 24 cards.
+    pub deck: DeckOptions,
+    pub bird_rank: BirdRank,
+    /// None means every Joker keeps the rank and points given in the deck.
+    pub later_joker: Option<LaterJokerOptions>,
+    pub duplicate_winner: DuplicateWinner,
+    pub hearts: HeartsOptions,
+    pub spades: SpadesOptions,
+    pub euchre: EuchreOptions,
+    /// Seeds the shuffles and the bots, so a match with the same human play
+    /// replays the same. None picks a seed at random. Either way it's logged.
+    pub seed: Option<u64>,
+    /// The game ends after the hand in which a player reaches this score.
+    pub winning_score: Points,
+    /// If the makers reach the winning score, they win even when the
+    /// defenders reached it with more.
+    pub makers_go_out_first: bool,
+    pub tie_break: TieBreak,
 }
 
 impl GameOptions {
     pub fn new() -> Self {
         Self {
+            rules: RulesKind::HundredFifty,
+            player_count: 4,
             hand_size: 9,
             nest_size: 2,
             nest_face_up: 0,
+            deal: DealPattern {
+                packet_sizes: vec![1],
+                nest: NestDeal::First,
+                bidding: BidTiming::DuringDeal(5),
+            },
             makers_points_awarded_for_win: PointsAwarded::PointsTakenWithMultiplier(1),
             makers_points_awarded_for_loss: PointsAwarded::Fixed(0),
             defenders_points_awarded_for_win: PointsAwarded::PointsTakenWithMultiplier(1),
             defenders_points_awarded_for_loss: PointsAwarded::PointsTakenWithMultiplier(1),
+            all_tricks_bonus: 0,
+            all_points_bonus: 0,
+            hand_score_cap: None,
+            defenders_score_only_below_game: false,
+            nest_owner: NestOwner::LastTrickWinner,
+            nest_points_multiplier: 1,
             nest_points_bonus: 10,
+            discard_rules: DiscardRules {
+                no_counters: false,
+                no_trump: false,
+                counters_to_defenders: false,
+            },
+            partner_kind: PartnerKind::Across,
+            alone_multiplier: None,
+            bid_minimum: 70,
+            bid_increment: 5,
+            bid_maximum: 130,
+            all_pass: AllPassPolicy::Redeal,
+            trump_rules: TrumpRules {
+                allow_no_trump: false,
+                must_trump_when_void: false,
+                must_over_trump: false,
+                trump_must_be_broken: false,
+            },
+            renege: RenegeRules {
+                allowed: false,
+                penalty: 50,
+                false_call_penalty: 20,
+            },
+            deck: DeckOptions {
+                low_rank: 5,
+                high_rank: 14,
+                removed_ranks: vec![6],
+                copies: 1,
+                rank_points: BTreeMap::from([(5, 5), (10, 10), (14, 15)]),
+                suit_points: Vec::new(),
+                // In this game the rank and value of the two Jokers depend on which
+                // one is played first. These are the values for the first-played.
+                special_cards: vec![SpecialCardOptions {
+                    suit: CardSuit::Joker,
+                    count: 2,
+                    rank: 15,
+                    points: 0,
+                }],
+            },
+            bird_rank: BirdRank::TenAndAHalf,
+            // The second Joker played becomes the lowest trump.
+            later_joker: Some(LaterJokerOptions {
+                game_rank: 4.5,
+                points: 0,
+            }),
+            duplicate_winner: DuplicateWinner::FirstPlayed,
+            hearts: HeartsOptions {
+                pass_size: 3,
+                moon_shot: MoonShot::AddToOthers,
+            },
+            spades: SpadesOptions {
+                nil: true,
+                nil_bonus: 100,
+                blind_nil: true,
+                blind_nil_bonus: 200,
+                bag_limit: 10,
+                bag_penalty: 100,
+                losing_score: -200,
+            },
+            euchre: EuchreOptions {
+                march: 2,
+                alone_march: 4,
+                euchre: 2,
+            },
+            seed: None,
+            winning_score: 500,
+            makers_go_out_first: true,
+            tie_break: TieBreak::PlayOn,
+        }
+    }
+
+    /// The defaults, with the hand size, nest size and partnerships suited to
+    /// the table. 3 players play cutthroat, 5 call a partner and 6 play in
+    /// teams of two.
+    pub fn for_player_count(player_count: PlayerId) -> Self {
+        let mut options = Self::new();
+        options.player_count = player_count;
+        match player_count {
+            3 => {
+                options.hand_size = 12;
+                options.partner_kind = PartnerKind::None;
+            }
+            5 => {
+                options.hand_size = 7;
+                options.nest_size = 3;
+                options.partner_kind = PartnerKind::Called;
+            }
+            6 => {
+                options.hand_size = 6;
+                options.partner_kind = PartnerKind::Across;
+            }
+            _ => {}
+        }
+        options
+    }
+
+    /// The whole 52-card deck dealt to four players, 13 each, with no nest.
+    fn four_hands_of_thirteen() -> Self {
+        let mut options = Self::new();
+        options.hand_size = 13;
+        options.nest_size = 0;
+        options.deal = DealPattern {
+            packet_sizes: vec![1],
+            nest: NestDeal::Last,
+            bidding: BidTiming::AfterDeal,
+        };
+        options
+    }
+
+    /// Hearts for four, played to 100.
+    pub fn hearts() -> Self {
+        let mut options = Self::four_hands_of_thirteen();
+        options.rules = RulesKind::Hearts;
+        options.partner_kind = PartnerKind::None;
+        options.winning_score = 100;
+        options
+    }
+
+    /// Spades for two partnerships of two, played to 500.
+    pub fn spades() -> Self {
+        let mut options = Self::four_hands_of_thirteen();
+        options.rules = RulesKind::Spades;
+        options.partner_kind = PartnerKind::Across;
+        options.winning_score = 500;
+        options
+    }
+
+    /// Euchre for two partnerships of two, played to 10. The 24-card deck is
+    /// dealt in packets of 3 and 2, and the nest is the turned card. The three
+    /// cards left in the deck join it after the dealer picks it up.
+    pub fn euchre() -> Self {
+        let mut options = Self::new();
+        options.rules = RulesKind::Euchre;
+        options.hand_size = 5;
+        options.nest_size = 1;
+        options.nest_face_up = 1;
+        options.deal = DealPattern {
+            packet_sizes: vec![3, 2],
+            nest: NestDeal::Last,
+            bidding: BidTiming::AfterDeal,
+        };
+        options.partner_kind = PartnerKind::Across;
+        options.winning_score = 10;
+        options
+    }
+
+    /// The preset for each game.
+    pub fn for_rules(rules: RulesKind) -> Self {
+        match rules {
+            RulesKind::HundredFifty => Self::new(),
+            RulesKind::Hearts => Self::hearts(),
+            RulesKind::Spades => Self::spades(),
+            RulesKind::Euchre => Self::euchre(),
+        }
+    }
+
+    /// Checks the table and that the deck has enough cards for the hands and
+    /// the nest. Any extra cards join the nest after the exchange.
+    pub fn validate(&self) -> Result<(), String> {
+        let player_count = self.player_count;
+        if !(3..=6).contains(&player_count) {
+            return Err(format!("player_count {} is not 3 to 6", player_count));
+        }
+        match self.partner_kind {
+            PartnerKind::Across | PartnerKind::Alternate if player_count % 2 != 0 => {
+                return Err(format!(
+                    "partner_kind {:?} needs an even player_count, not {}",
+                    self.partner_kind, player_count
+                ));
+            }
+            _ => {}
+        }
+        if let Some(multiplier) = self.alone_multiplier {
+            if multiplier < 1 {
+                return Err(format!("alone_multiplier {} is below 1", multiplier));
+            }
+        }
+        if self.bid_increment < 1 {
+            return Err(format!("bid_increment {} is below 1", self.bid_increment));
+        }
+        if self.deck.low_rank > self.deck.high_rank {
+            return Err(format!(
+                "deck low_rank {} is above high_rank {}",
+                self.deck.low_rank, self.deck.high_rank
+            ));
+        }
+        if self.deck.copies == 0 {
+            return Err("deck copies must be at least 1".to_string());
         }
+        for special in &self.deck.special_cards {
+            if !special.suit.is_special() {
+                return Err(format!("{:?} is not a special card suit", special.suit));
+            }
+        }
+        if self.rules == RulesKind::Hearts {
+            if player_count != 4 || self.partner_kind != PartnerKind::None {
+                return Err("Hearts is played by 4 players with partner_kind None".to_string());
+            }
+            if self.renege.allowed {
+                return Err("renege is not supported with Hearts".to_string());
+            }
+            if self.hearts.pass_size == 0 || self.hearts.pass_size > self.hand_size {
+                return Err(format!(
+                    "hearts pass_size {} is not 1 to hand_size {}",
+                    self.hearts.pass_size, self.hand_size
+                ));
+            }
+        }
+        if self.rules == RulesKind::Spades
+            && (player_count != 4 || self.partner_kind != PartnerKind::Across)
+        {
+            return Err("Spades is played by 4 players with partner_kind Across".to_string());
+        }
+        if self.rules == RulesKind::Spades && self.spades.bag_limit == 0 {
+            return Err("spades bag_limit must be at least 1".to_string());
+        }
+        if self.rules == RulesKind::Euchre {
+            if player_count != 4 || self.partner_kind != PartnerKind::Across {
+                return Err("Euchre is played by 4 players with partner_kind Across".to_string());
+            }
+            if self.nest_size != 1 || self.nest_face_up != 1 {
+                return Err(
+                    "Euchre needs nest_size 1 and nest_face_up 1 for the turned card".to_string(),
+                );
+            }
+            if let AllPassPolicy::ReopenLower(_) = self.all_pass {
+                return Err("Euchre can't reopen the bidding lower".to_string());
+            }
+        }
+        if self.deal.packet_sizes.is_empty() || self.deal.packet_sizes.contains(&0) {
+            return Err("deal packet_sizes must be one or more non-zero sizes".to_string());
+        }
+        if self.deal.bidding == BidTiming::DuringDeal(0) {
+            return Err("deal bidding DuringDeal needs at least 1 round".to_string());
+        }
+        if self.discard_rules.no_counters && self.discard_rules.counters_to_defenders {
+            return Err(
+                "discard_rules no_counters and counters_to_defenders can't both be set".to_string(),
+            );
+        }
+        let needed = self.hand_size as usize * player_count + self.nest_size as usize;
+        let card_count = self.rules.rules().create_cards(self).len();
+        if needed > card_count {
+            return Err(format!(
+                "hand_size {} x {} players + nest_size {} is {}, but the deck has only {} cards",
+                self.hand_size, player_count, self.nest_size, needed, card_count
+            ));
+        }
+        Ok(())
     }
 
     fn read_contents_from_file(path: &str) -> String {
@@ -56,6 +616,15 @@ impl GameOptions {
         contents
     }
 
+    /// The options in default.txt. The file is written from GameOptions::new
+    /// the first time, then left alone so it can be edited.
+    pub fn read_default() -> GameOptions {
+        if !Path::new("default.txt").exists() {
+            GameOptions::new().write_to_yaml("default.txt");
+        }
+        GameOptions::read_from_yaml("default.txt")
+    }
+
     pub fn read_from_yaml(path: &str) -> GameOptions {
         let contents = GameOptions::read_contents_from_file(path);
 
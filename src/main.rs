@@ -1,4 +1,5 @@
 mod animators;
+mod bid;
 mod bid_selector;
 mod bot;
 mod bot_monte;
@@ -8,10 +9,19 @@ mod card_update;
 mod card_view;
 mod controller;
 mod game;
+mod game_error;
 mod game_options;
 mod image;
 mod image_button;
 mod player;
+mod rules;
+mod rules_euchre;
+mod rules_hearts;
+mod rules_hundred_fifty;
+mod rules_spades;
+mod save;
+mod scoring;
+mod team;
 mod text_button;
 mod texture_loader;
 mod transform;
@@ -21,8 +31,12 @@ mod view_geom;
 mod view_trait;
 
 use controller::Controller;
+use game::Game;
+use game_options::GameOptions;
 use notan::prelude::*;
 use once_cell::sync::Lazy;
+use player::PlayerId;
+use rules::RulesKind;
 use texture_loader::TextureLoader;
 
 // Globals
@@ -61,11 +75,72 @@ fn main() -> Result<(), String> {
         .build()
 }
 
+/// The number after --seed on the command line, to replay a match.
+fn seed_arg() -> Option<u64> {
+    let args: Vec<String> = std::env::args().collect();
+    let idx = args.iter().position(|arg| arg == "--seed")?;
+    let seed = args.get(idx + 1).expect("--seed needs a number");
+    Some(seed.parse().expect("--seed needs a number"))
+}
+
+/// The save file after --load on the command line, to resume a game. With no
+/// path given, the one the game saves to.
+fn load_arg() -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    let idx = args.iter().position(|arg| arg == "--load")?;
+    let path = args.get(idx + 1).filter(|arg| !arg.starts_with("--"));
+    Some(path.map_or(save::SAVE_PATH.to_string(), |path| path.clone()))
+}
+
+/// The game named after --rules on the command line.
+fn rules_arg() -> Option<RulesKind> {
+    let args: Vec<String> = std::env::args().collect();
+    let idx = args.iter().position(|arg| arg == "--rules")?;
+    let name = args.get(idx + 1).map(|arg| arg.as_str());
+    match name {
+        Some("hundred_fifty") => Some(RulesKind::HundredFifty),
+        Some("hearts") => Some(RulesKind::Hearts),
+        Some("spades") => Some(RulesKind::Spades),
+        Some("euchre") => Some(RulesKind::Euchre),
+        _ => panic!("--rules needs one of: hundred_fifty, hearts, spades, euchre"),
+    }
+}
+
+/// The number after --players on the command line. Hundred Fifty seats 3 to 6.
+fn players_arg() -> Option<PlayerId> {
+    let args: Vec<String> = std::env::args().collect();
+    let idx = args.iter().position(|arg| arg == "--players")?;
+    let count = args.get(idx + 1).expect("--players needs a number");
+    Some(count.parse().expect("--players needs a number"))
+}
+
+/// The options for a new game: the preset for --rules or --players, or else
+/// default.txt. --seed overrides the seed.
+fn new_game_options() -> GameOptions {
+    let mut options = match (rules_arg(), players_arg()) {
+        (None | Some(RulesKind::HundredFifty), Some(count)) => GameOptions::for_player_count(count),
+        (Some(rules), Some(_)) => panic!("--players is only for hundred_fifty, not {:?}", rules),
+        (Some(rules), None) => GameOptions::for_rules(rules),
+        (None, None) => GameOptions::read_default(),
+    };
+    if let Some(seed) = seed_arg() {
+        options.seed = Some(seed);
+    }
+    options
+}
+
 fn setup(assets: &mut Assets, gfx: &mut Graphics) -> Controller {
     let path = std::env::current_dir().expect("whoops");
     println!("Current directory: {}", path.display());
 
-    Controller::new(assets, gfx)
+    let game = match load_arg() {
+        Some(path) => match save::read_from_yaml(&path) {
+            Ok(game) => game,
+            Err(e) => panic!("Error loading saved game: {}", e),
+        },
+        None => Game::with_options(new_game_options()),
+    };
+    Controller::new(assets, gfx, game)
 }
 
 fn event(controller: &mut Controller, event: Event) {
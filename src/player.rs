@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 use crate::bot::BotKind;
 use crate::card::{CardId, Points};
 use crate::trick::Trick;
@@ -5,21 +7,25 @@ use crate::trick::Trick;
 // Would prefer to use u8, but usize is better due to frequent use when vec indexing.
 pub type PlayerId = usize;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum PlayerKind {
     Maker,
     Defender,
+    Unknown, // might be the maker's called partner, not yet revealed
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Player {
     pub kind: Option<PlayerKind>,
-    pub partner: Option<PlayerId>,
+    /// Everyone on this player's side this hand, not including the player.
+    pub partners: Vec<PlayerId>,
     pub active: bool, // alive in hand?
     pub hand: Vec<CardId>,
     //pub bid: Option<CardSuit>,
     pub tricks: Vec<Trick>,
     pub points_this_hand: Points,
+    /// The part of points_this_hand that came from the nest.
+    pub nest_points: Points,
     pub score: Points,
     pub bot_kind: Option<BotKind>,
 }
@@ -28,12 +34,13 @@ impl Player {
     pub fn new() -> Self {
         Self {
             kind: None,
-            partner: None,
+            partners: Vec::new(),
             active: true,
             hand: Vec::new(),
             //bid: None,
             tricks: Vec::new(),
             points_this_hand: 0,
+            nest_points: 0,
             score: 0,
             bot_kind: None,
         }
@@ -41,11 +48,13 @@ impl Player {
 
     pub fn reset(&mut self) {
         self.kind = None;
+        self.partners.clear();
         self.active = true;
         self.hand.clear();
         //self.bid = None;
         self.tricks.clear();
         self.points_this_hand = 0;
+        self.nest_points = 0;
     }
 
     pub fn add_to_hand(&mut self, id: CardId) {
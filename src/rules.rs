@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::{Card, CardId, CardSuit, FaceRank, GameRank};
+use crate::game::Game;
+use crate::game_options::GameOptions;
+use crate::player::PlayerId;
+use crate::rules_euchre::Euchre;
+use crate::rules_hearts::Hearts;
+use crate::rules_hundred_fifty::HundredFifty;
+use crate::rules_spades::Spades;
+use crate::scoring::ScoreBreakdown;
+use crate::trick::Trick;
+
+/// The trick-taking game being played. See GameOptions::rules.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RulesKind {
+    HundredFifty,
+    Hearts,
+    Spades,
+    Euchre,
+}
+
+impl RulesKind {
+    pub fn rules(&self) -> Box<dyn Rules> {
+        match self {
+            RulesKind::HundredFifty => Box::new(HundredFifty {}),
+            RulesKind::Hearts => Box::new(Hearts {}),
+            RulesKind::Spades => Box::new(Spades {}),
+            RulesKind::Euchre => Box::new(Euchre {}),
+        }
+    }
+}
+
+/// The steps of a hand. Every hand starts with Deal and ends with Play.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Phase {
+    Deal,
+    /// Each player passes cards to another, as in Hearts. Uses the nest
+    /// exchange, one player at a time.
+    PassCards,
+    /// Happens during the deal, according to GameOptions::deal.
+    Bidding,
+    /// Each player in turn bids the tricks they'll take, as in Spades.
+    TrickBidding,
+    /// Two rounds around the table, as in Euchre: order up the suit of the
+    /// turned nest card or pass, then name another suit or pass.
+    TrumpBidding,
+    NestExchange,
+    ChooseTrump,
+    CallPartner,
+    GoAlone,
+    Play,
+}
+
+/// The parts of a trick-taking game that differ from game to game. Game
+/// runs the deal, the phases and the tricks, and asks its Rules for the rest.
+pub trait Rules {
+    /// The phases of a hand in order.
+    fn phases(&self, options: &GameOptions) -> Vec<Phase>;
+
+    /// Every card in the deck. Game assigns the ids.
+    fn create_cards(&self, options: &GameOptions) -> Vec<Card>;
+
+    /// The active player's cards that may be played to the trick.
+    fn legal_card_ids(&self, game: &Game) -> Vec<CardId>;
+
+    /// Whether card beats the trick's winning card. Only asked once the trick
+    /// has a lead.
+    fn takes_lead(&self, trick: &Trick, card: &Card) -> bool {
+        trick.takes_lead(card)
+    }
+
+    /// The rank card takes among the trump once suit is named, or None if it
+    /// isn't trump. Special cards are always trump and aren't asked about.
+    fn trump_rank(&self, card: &Card, suit: CardSuit) -> Option<GameRank> {
+        (card.suit == suit).then_some(card.game_rank)
+    }
+
+    /// The suit that's trump every hand, if the game has one.
+    fn fixed_trump(&self) -> Option<CardSuit> {
+        None
+    }
+
+    /// Who leads the first trick of the hand.
+    fn first_leader(&self, game: &Game) -> PlayerId;
+
+    /// Each player's score for the finished hand. Points taken outside the
+    /// tricks, like the nest, are added to the players here.
+    fn score_hand(&self, game: &mut Game) -> Vec<ScoreBreakdown>;
+
+    /// Scores are penalties and the lowest total wins.
+    fn low_score_wins(&self) -> bool {
+        false
+    }
+}
+
+/// The standard 52-card deck, aces high.
+pub fn standard_cards() -> Vec<Card> {
+    let ranks: Vec<FaceRank> = (2..=14).collect();
+    suited_cards(&ranks)
+}
+
+/// A card of each rank in each of the four suits.
+pub fn suited_cards(ranks: &[FaceRank]) -> Vec<Card> {
+    let mut cards = Vec::new();
+    for rank in ranks.iter().copied() {
+        cards.push(Card::new(CardSuit::Club, rank));
+        cards.push(Card::new(CardSuit::Diamond, rank));
+        cards.push(Card::new(CardSuit::Heart, rank));
+        cards.push(Card::new(CardSuit::Spade, rank));
+    }
+    cards
+}
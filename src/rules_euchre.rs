@@ -0,0 +1,90 @@
+use crate::card::{Card, CardId, CardSuit, FaceRank, GameRank};
+use crate::game::Game;
+use crate::game_options::GameOptions;
+use crate::player::{PlayerId, PlayerKind};
+use crate::rules::{self, Phase, Rules};
+use crate::scoring::{self, ScoreBreakdown};
+
+const JACK: FaceRank = 11;
+const RIGHT_BOWER: GameRank = 16.0;
+const LEFT_BOWER: GameRank = 15.0;
+
+/// Euchre: trump is bid on a turned card, the jacks of trump's color are the
+/// top trump, and a side needs most of the five tricks.
+pub struct Euchre {}
+
+impl Euchre {
+    /// The other suit of the same color.
+    fn same_color(suit: CardSuit) -> CardSuit {
+        match suit {
+            CardSuit::Club => CardSuit::Spade,
+            CardSuit::Spade => CardSuit::Club,
+            CardSuit::Diamond => CardSuit::Heart,
+            CardSuit::Heart => CardSuit::Diamond,
+            CardSuit::Joker | CardSuit::Bird => suit,
+        }
+    }
+}
+
+impl Rules for Euchre {
+    fn phases(&self, _options: &GameOptions) -> Vec<Phase> {
+        vec![
+            Phase::Deal,
+            Phase::TrumpBidding,
+            Phase::NestExchange,
+            Phase::GoAlone,
+            Phase::Play,
+        ]
+    }
+
+    /// The nines through the aces.
+    fn create_cards(&self, _options: &GameOptions) -> Vec<Card> {
+        let ranks: Vec<FaceRank> = (9..=14).collect();
+        rules::suited_cards(&ranks)
+    }
+
+    /// Follow suit if possible. The left bower follows trump, not its own suit.
+    fn legal_card_ids(&self, game: &Game) -> Vec<CardId> {
+        let card_count_matching_lead = game.card_count_matching_lead();
+        game.active_hand()
+            .iter()
+            .copied()
+            .filter(|id| {
+                game.trick
+                    .is_eligible(&game.cards[*id], card_count_matching_lead)
+            })
+            .collect()
+    }
+
+    /// The jack of trump is the right bower and the jack of the same color
+    /// the left bower. Both rank above the ace.
+    fn trump_rank(&self, card: &Card, suit: CardSuit) -> Option<GameRank> {
+        match card.face_rank == JACK {
+            true if card.suit == suit => Some(RIGHT_BOWER),
+            true if card.suit == Euchre::same_color(suit) => Some(LEFT_BOWER),
+            _ => (card.suit == suit).then_some(card.game_rank),
+        }
+    }
+
+    /// The player left of the dealer.
+    fn first_leader(&self, game: &Game) -> PlayerId {
+        (game.dealer + 1) % game.player_count
+    }
+
+    fn score_hand(&self, game: &mut Game) -> Vec<ScoreBreakdown> {
+        let makers_tricks = game.side_take(game.maker.unwrap()).tricks;
+        let (makers, defenders) = scoring::euchre_scores(
+            &game.options.euchre,
+            makers_tricks,
+            game.options.hand_size as usize,
+            game.alone,
+        );
+        game.players
+            .iter()
+            .map(|player| match player.kind {
+                Some(PlayerKind::Maker) => makers.clone(),
+                _ => defenders.clone(),
+            })
+            .collect()
+    }
+}
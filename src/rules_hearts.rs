@@ -0,0 +1,152 @@
+use crate::card::{Card, CardId, CardSuit, FaceRank};
+use crate::game::Game;
+use crate::game_options::GameOptions;
+use crate::player::PlayerId;
+use crate::rules::{self, Phase, Rules};
+use crate::scoring::{self, ScoreBreakdown};
+
+const TWO: FaceRank = 2;
+const QUEEN: FaceRank = 12;
+
+/// Hearts: no bidding and no trump. Each heart taken is a penalty point and
+/// the queen of spades is 13. The game ends when someone reaches the winning
+/// score, and the lowest score wins.
+pub struct Hearts {}
+
+/// Where the cards go in the pass before each hand.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PassDirection {
+    Left,
+    Right,
+    Across,
+    /// No pass this hand.
+    Hold,
+}
+
+impl PassDirection {
+    /// Left, right, across, then a hold hand, repeating.
+    pub fn for_hand(hands_played: usize) -> Self {
+        match hands_played % 4 {
+            0 => PassDirection::Left,
+            1 => PassDirection::Right,
+            2 => PassDirection::Across,
+            _ => PassDirection::Hold,
+        }
+    }
+
+    /// Who player p passes to. None on a hold hand.
+    pub fn target(&self, p: PlayerId, player_count: PlayerId) -> Option<PlayerId> {
+        match self {
+            PassDirection::Left => Some((p + 1) % player_count),
+            PassDirection::Right => Some((p + player_count - 1) % player_count),
+            PassDirection::Across => Some((p + player_count / 2) % player_count),
+            PassDirection::Hold => None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            PassDirection::Left => "left",
+            PassDirection::Right => "right",
+            PassDirection::Across => "across",
+            PassDirection::Hold => "hold",
+        }
+    }
+}
+
+impl Hearts {
+    fn is_two_of_clubs(card: &Card) -> bool {
+        card.suit == CardSuit::Club && card.face_rank == TWO
+    }
+
+    /// Hearts are broken once one has been played to a trick this hand.
+    fn hearts_broken(game: &Game) -> bool {
+        game.players
+            .iter()
+            .flat_map(|player| &player.tricks)
+            .chain(std::iter::once(&game.trick))
+            .flat_map(|trick| trick.card_ids.iter().flatten())
+            .any(|id| game.cards[*id].suit == CardSuit::Heart)
+    }
+
+    fn filtered(game: &Game, keep: impl Fn(&Card) -> bool) -> Vec<CardId> {
+        game.active_hand()
+            .iter()
+            .copied()
+            .filter(|id| keep(&game.cards[*id]))
+            .collect()
+    }
+}
+
+impl Rules for Hearts {
+    fn phases(&self, _options: &GameOptions) -> Vec<Phase> {
+        vec![Phase::Deal, Phase::PassCards, Phase::Play]
+    }
+
+    /// A standard 52-card deck.
+    fn create_cards(&self, _options: &GameOptions) -> Vec<Card> {
+        let mut cards = rules::standard_cards();
+        for card in &mut cards {
+            card.points = match (card.suit, card.face_rank) {
+                (CardSuit::Heart, _) => 1,
+                (CardSuit::Spade, QUEEN) => 13,
+                _ => 0,
+            };
+        }
+        cards
+    }
+
+    /// Follow suit if possible. The two of clubs leads the first trick, and
+    /// no points may be thrown on it unless the hand holds nothing else.
+    /// Hearts can't be led until broken, unless the hand holds only hearts.
+    fn legal_card_ids(&self, game: &Game) -> Vec<CardId> {
+        let hand = game.active_hand();
+        let first_trick = game.tricks_played == 0;
+
+        let lead_card = match &game.trick.lead_card {
+            Some(card) if !game.trick.is_empty => card,
+            _ => {
+                let two = Hearts::filtered(game, Hearts::is_two_of_clubs);
+                if first_trick && !two.is_empty() {
+                    return two;
+                }
+                let not_hearts = Hearts::filtered(game, |card| card.suit != CardSuit::Heart);
+                if !Hearts::hearts_broken(game) && !not_hearts.is_empty() {
+                    return not_hearts;
+                }
+                return hand.clone();
+            }
+        };
+
+        let following = Hearts::filtered(game, |card| card.follows(lead_card));
+        if !following.is_empty() {
+            return following;
+        }
+        let blanks = Hearts::filtered(game, |card| card.points == 0);
+        if first_trick && !blanks.is_empty() {
+            return blanks;
+        }
+        hand.clone()
+    }
+
+    /// Whoever holds the two of clubs.
+    fn first_leader(&self, game: &Game) -> PlayerId {
+        (0..game.player_count)
+            .find(|p| {
+                game.players[*p]
+                    .hand
+                    .iter()
+                    .any(|id| Hearts::is_two_of_clubs(&game.cards[*id]))
+            })
+            .unwrap_or(game.active_player)
+    }
+
+    fn score_hand(&self, game: &mut Game) -> Vec<ScoreBreakdown> {
+        let taken: Vec<_> = game.players.iter().map(|p| p.points_this_hand).collect();
+        scoring::hearts_scores(&game.options.hearts, &taken)
+    }
+
+    fn low_score_wins(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,141 @@
+use crate::card::{Card, CardId, CardSuit};
+use crate::game::Game;
+use crate::game_options::{GameOptions, PartnerKind};
+use crate::player::{PlayerId, PlayerKind};
+use crate::rules::{self, Phase, Rules};
+use crate::scoring::{self, ScoreBreakdown};
+
+/// Hundred Fifty, the Rook-style game this engine started with: a point-bid
+/// auction, a nest exchange, then trump named by the maker.
+pub struct HundredFifty {}
+
+impl HundredFifty {
+    /// Narrows the cards that follow suit according to GameOptions::trump_rules.
+    fn apply_trump_rules(
+        &self,
+        game: &Game,
+        mut ids: Vec<CardId>,
+        card_count_matching_lead: usize,
+    ) -> Vec<CardId> {
+        let rules = &game.options.trump_rules;
+        let trump_ids: Vec<CardId> = ids
+            .iter()
+            .copied()
+            .filter(|id| game.cards[*id].is_trump)
+            .collect();
+
+        if game.trick.is_empty {
+            if rules.trump_must_be_broken && !game.trump_broken && trump_ids.len() < ids.len() {
+                ids.retain(|id| !trump_ids.contains(id));
+            }
+            return ids;
+        }
+
+        if rules.must_trump_when_void && card_count_matching_lead == 0 && !trump_ids.is_empty() {
+            ids = trump_ids.clone();
+        }
+        if rules.must_over_trump {
+            let trump_winning = match &game.trick.winning_card {
+                Some(card) => card.is_trump,
+                None => false,
+            };
+            let over_trump_ids: Vec<CardId> = trump_ids
+                .iter()
+                .copied()
+                .filter(|id| self.takes_lead(&game.trick, &game.cards[*id]))
+                .collect();
+            if trump_winning && !over_trump_ids.is_empty() {
+                ids.retain(|id| !trump_ids.contains(id) || over_trump_ids.contains(id));
+            }
+        }
+        ids
+    }
+}
+
+impl Rules for HundredFifty {
+    fn phases(&self, options: &GameOptions) -> Vec<Phase> {
+        let mut phases = vec![
+            Phase::Deal,
+            Phase::Bidding,
+            Phase::NestExchange,
+            Phase::ChooseTrump,
+        ];
+        match options.partner_kind {
+            PartnerKind::Called => phases.push(Phase::CallPartner),
+            // Going alone needs fixed partners to leave out.
+            PartnerKind::Across | PartnerKind::Alternate if options.alone_multiplier.is_some() => {
+                phases.push(Phase::GoAlone)
+            }
+            _ => {}
+        }
+        phases.push(Phase::Play);
+        phases
+    }
+
+    fn create_cards(&self, options: &GameOptions) -> Vec<Card> {
+        let deck = &options.deck;
+        let mut cards = Vec::new();
+        for copy in 0..deck.copies {
+            for mut card in rules::suited_cards(&deck.ranks()) {
+                card.copy = copy;
+                cards.push(card);
+            }
+        }
+
+        // Assign card points.
+        for card in &mut cards {
+            card.points = deck.points_for(card.suit, card.face_rank);
+        }
+
+        // Add special cards, such as Jokers and the Bird.
+        for special in &deck.special_cards {
+            for copy in 0..special.count {
+                let mut card = Card::new(special.suit, special.rank);
+                card.copy = copy;
+                card.points = special.points;
+                if card.suit == CardSuit::Bird {
+                    card.game_rank = options.bird_rank.game_rank();
+                }
+                cards.push(card);
+            }
+        }
+        cards
+    }
+
+    fn legal_card_ids(&self, game: &Game) -> Vec<CardId> {
+        let mut ids = Vec::new();
+        let card_count_matching_lead = game.card_count_matching_lead();
+        for id in game.active_hand() {
+            let card = game.cards.get(*id).unwrap();
+            if game.trick.is_eligible(card, card_count_matching_lead) {
+                ids.push(*id);
+            }
+        }
+        self.apply_trump_rules(game, ids, card_count_matching_lead)
+    }
+
+    /// The maker, who is still the active player after naming trump.
+    fn first_leader(&self, game: &Game) -> PlayerId {
+        game.active_player
+    }
+
+    /// Awards the nest, then scores the makers and each defending side. With
+    /// more than one defending side, as in cutthroat, each scores on its own
+    /// points.
+    fn score_hand(&self, game: &mut Game) -> Vec<ScoreBreakdown> {
+        game.award_nest();
+        let hand = game.hand_summary();
+        (0..game.player_count)
+            .map(|p| match game.players[p].kind {
+                Some(PlayerKind::Maker) => scoring::makers_score(&game.options, &hand),
+                Some(PlayerKind::Defender) => scoring::defenders_score(
+                    &game.options,
+                    &hand,
+                    game.side_take(p),
+                    game.players[p].score,
+                ),
+                _ => ScoreBreakdown::default(),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,72 @@
+use crate::card::{Card, CardId, CardSuit};
+use crate::game::Game;
+use crate::game_options::GameOptions;
+use crate::player::PlayerId;
+use crate::rules::{self, Phase, Rules};
+use crate::scoring::{self, ScoreBreakdown};
+
+/// Spades: each player bids the tricks they'll take, spades are always trump,
+/// and partnerships score on making their combined bid.
+pub struct Spades {}
+
+impl Rules for Spades {
+    fn phases(&self, _options: &GameOptions) -> Vec<Phase> {
+        vec![Phase::Deal, Phase::TrickBidding, Phase::Play]
+    }
+
+    /// A standard 52-card deck. Cards carry no points; the tricks count.
+    fn create_cards(&self, _options: &GameOptions) -> Vec<Card> {
+        rules::standard_cards()
+    }
+
+    /// Follow suit if possible. Spades can't be led until one has been played,
+    /// unless the hand holds only spades.
+    fn legal_card_ids(&self, game: &Game) -> Vec<CardId> {
+        let card_count_matching_lead = game.card_count_matching_lead();
+        let mut ids: Vec<CardId> = game
+            .active_hand()
+            .iter()
+            .copied()
+            .filter(|id| {
+                game.trick
+                    .is_eligible(&game.cards[*id], card_count_matching_lead)
+            })
+            .collect();
+
+        let all_trump = ids.iter().all(|id| game.cards[*id].is_trump);
+        if game.trick.is_empty && !game.trump_broken && !all_trump {
+            ids.retain(|id| !game.cards[*id].is_trump);
+        }
+        ids
+    }
+
+    fn fixed_trump(&self) -> Option<CardSuit> {
+        Some(CardSuit::Spade)
+    }
+
+    /// The player left of the dealer.
+    fn first_leader(&self, game: &Game) -> PlayerId {
+        (game.dealer + 1) % game.player_count
+    }
+
+    /// Scores each partnership on its bids and carries its bags to the next
+    /// hand. Partners share the score.
+    fn score_hand(&self, game: &mut Game) -> Vec<ScoreBreakdown> {
+        let mut breakdowns = vec![ScoreBreakdown::default(); game.player_count];
+        for t in 0..game.teams.len() {
+            let members: Vec<_> = game.teams[t]
+                .players
+                .iter()
+                .map(|p| (game.trick_bids[*p].unwrap(), game.players[*p].tricks.len()))
+                .collect();
+            let team = &mut game.teams[t];
+            let (breakdown, bags) =
+                scoring::spades_score(&game.options.spades, &members, team.bags);
+            team.bags = bags;
+            for p in &team.players {
+                breakdowns[*p] = breakdown.clone();
+            }
+        }
+        breakdowns
+    }
+}
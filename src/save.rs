@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+
+use crate::game::Game;
+
+/// Increase when a change to Game or anything it holds breaks older saves.
+pub const SAVE_VERSION: u32 = 1;
+
+pub const SAVE_PATH: &str = "saved_game.txt";
+
+/// A game in progress, as written to a save file.
+#[derive(Serialize, Deserialize)]
+struct SaveFile<G> {
+    version: u32,
+    game: G,
+}
+
+/// Write the complete state of the game, to resume later with read_from_yaml.
+pub fn write_to_yaml(game: &Game, path: &str) -> Result<(), String> {
+    let save = SaveFile {
+        version: SAVE_VERSION,
+        game,
+    };
+    let serialized = serde_yaml::to_string(&save).map_err(|e| e.to_string())?;
+
+    let mut file = File::create(path).map_err(|e| format!("{path}: {e}"))?;
+    write!(file, "{}", serialized).map_err(|e| format!("{path}: {e}"))
+}
+
+/// Read a game saved by write_to_yaml. The version is checked before the
+/// rest of the file is read.
+pub fn read_from_yaml(path: &str) -> Result<Game, String> {
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut contents))
+        .map_err(|e| format!("{path}: {e}"))?;
+
+    let value: serde_yaml::Value = serde_yaml::from_str(&contents).map_err(|e| e.to_string())?;
+    let version = value.get("version").and_then(|v| v.as_u64());
+    if version != Some(SAVE_VERSION as u64) {
+        return Err(format!(
+            "{path}: save version {version:?} is not {SAVE_VERSION}"
+        ));
+    }
+
+    let save: SaveFile<Game> = serde_yaml::from_value(value).map_err(|e| e.to_string())?;
+    Ok(save.game)
+}
+
+/// fastrand::Rng isn't serializable, so Game::rng is saved as its state.
+pub mod rng_state {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(rng: &fastrand::Rng, serializer: S) -> Result<S::Ok, S::Error> {
+        rng.get_seed().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<fastrand::Rng, D::Error> {
+        u64::deserialize(deserializer).map(fastrand::Rng::with_seed)
+    }
+}
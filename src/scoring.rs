@@ -0,0 +1,525 @@
+use serde::{Deserialize, Serialize};
+
+use crate::bid::TrickBid;
+use crate::card::Points;
+use crate::game_options::{
+    EuchreOptions, GameOptions, HeartsOptions, MoonShot, PointsAwarded, SpadesOptions,
+};
+
+/// The points and tricks one side took in a hand, nest included.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SideTake {
+    pub points: Points,
+    pub tricks: usize,
+}
+
+/// What's needed from a finished hand to score any side.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HandSummary {
+    pub contract: Points,
+    pub makers: SideTake,
+    /// Taken by all sides together.
+    pub total: SideTake,
+    /// The maker went alone. See GameOptions::alone_multiplier.
+    pub alone: bool,
+}
+
+impl HandSummary {
+    pub fn made(&self) -> bool {
+        self.makers.points >= self.contract
+    }
+}
+
+/// One line of a side's score for a hand.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ScoreItem {
+    /// From the PointsAwarded option for the side and outcome.
+    Awarded(Points),
+    AllTricksBonus(Points),
+    AllPointsBonus(Points),
+    /// What GameOptions::alone_multiplier adds to a lone maker's made contract.
+    AloneBonus(Points),
+    /// Negative. Brings the score down to GameOptions::hand_score_cap.
+    Cap(Points),
+    /// Negative. Cancels the score of defenders already at or above game.
+    AboveGame(Points),
+    /// Negative. Brings a side caught reneging down to minus the penalty.
+    Renege(Points),
+    /// Negative. For each renege a player on the side called wrongly.
+    FalseRenegeCall(Points),
+    /// Hearts: what shooting the moon moves, by HeartsOptions::moon_shot.
+    MoonShot(Points),
+    /// Spades: 10 a trick bid, plus or minus.
+    Contract(Points),
+    /// Spades: a nil or blind nil bid made, or negative for one failed.
+    Nil(Points),
+    /// Spades: 1 for each trick over the bid.
+    Bags(Points),
+    /// Negative. Spades: for each SpadesOptions::bag_limit bags.
+    BagPenalty(Points),
+    /// Euchre: the makers took every trick.
+    March(Points),
+    /// Euchre: the defenders stopped the makers taking most of the tricks.
+    Euchre(Points),
+}
+
+impl ScoreItem {
+    pub fn points(&self) -> Points {
+        match self {
+            ScoreItem::Awarded(p)
+            | ScoreItem::AllTricksBonus(p)
+            | ScoreItem::AllPointsBonus(p)
+            | ScoreItem::AloneBonus(p)
+            | ScoreItem::Cap(p)
+            | ScoreItem::AboveGame(p)
+            | ScoreItem::Renege(p)
+            | ScoreItem::FalseRenegeCall(p)
+            | ScoreItem::MoonShot(p)
+            | ScoreItem::Contract(p)
+            | ScoreItem::Nil(p)
+            | ScoreItem::Bags(p)
+            | ScoreItem::BagPenalty(p)
+            | ScoreItem::March(p)
+            | ScoreItem::Euchre(p) => *p,
+        }
+    }
+}
+
+/// A side's score for a hand, item by item.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    pub items: Vec<ScoreItem>,
+}
+
+impl ScoreBreakdown {
+    pub fn total(&self) -> Points {
+        self.items.iter().map(|item| item.points()).sum()
+    }
+}
+
+fn awarded(awarded: &PointsAwarded, side: SideTake, contract: Points) -> Points {
+    match awarded {
+        PointsAwarded::Fixed(p) => *p,
+        PointsAwarded::PointsTakenWithMultiplier(x) => side.points * x,
+        PointsAwarded::Bid => contract,
+        PointsAwarded::MinusBid => -contract,
+    }
+}
+
+/// Adds the shoot-the-moon bonuses and the cap shared by both sides.
+fn add_bonuses_and_cap(
+    options: &GameOptions,
+    hand: &HandSummary,
+    side: SideTake,
+    breakdown: &mut ScoreBreakdown,
+) {
+    if options.all_tricks_bonus != 0 && side.tricks > 0 && side.tricks == hand.total.tricks {
+        breakdown
+            .items
+            .push(ScoreItem::AllTricksBonus(options.all_tricks_bonus));
+    }
+    if options.all_points_bonus != 0 && side.points > 0 && side.points == hand.total.points {
+        breakdown
+            .items
+            .push(ScoreItem::AllPointsBonus(options.all_points_bonus));
+    }
+    if let Some(cap) = options.hand_score_cap {
+        let total = breakdown.total();
+        if total > cap {
+            breakdown.items.push(ScoreItem::Cap(cap - total));
+        }
+    }
+}
+
+pub fn makers_score(options: &GameOptions, hand: &HandSummary) -> ScoreBreakdown {
+    let points_awarded = match hand.made() {
+        true => &options.makers_points_awarded_for_win,
+        false => &options.makers_points_awarded_for_loss,
+    };
+    let awarded = awarded(points_awarded, hand.makers, hand.contract);
+    let mut breakdown = ScoreBreakdown {
+        items: vec![ScoreItem::Awarded(awarded)],
+    };
+    if let (true, true, Some(multiplier)) = (hand.alone, hand.made(), options.alone_multiplier) {
+        if multiplier > 1 {
+            breakdown
+                .items
+                .push(ScoreItem::AloneBonus(awarded * (multiplier - 1)));
+        }
+    }
+    add_bonuses_and_cap(options, hand, hand.makers, &mut breakdown);
+    breakdown
+}
+
+/// defenders is what this defending side took. score_before is its total
+/// before the hand, checked against GameOptions::defenders_score_only_below_game.
+pub fn defenders_score(
+    options: &GameOptions,
+    hand: &HandSummary,
+    defenders: SideTake,
+    score_before: Points,
+) -> ScoreBreakdown {
+    // The defenders win when the makers are set.
+    let points_awarded = match hand.made() {
+        true => &options.defenders_points_awarded_for_loss,
+        false => &options.defenders_points_awarded_for_win,
+    };
+    let mut breakdown = ScoreBreakdown {
+        items: vec![ScoreItem::Awarded(awarded(
+            points_awarded,
+            defenders,
+            hand.contract,
+        ))],
+    };
+    add_bonuses_and_cap(options, hand, defenders, &mut breakdown);
+
+    let total = breakdown.total();
+    if options.defenders_score_only_below_game && score_before >= options.winning_score && total > 0
+    {
+        breakdown.items.push(ScoreItem::AboveGame(-total));
+    }
+    breakdown
+}
+
+/// Hearts scores the points each player took, lower being better. A player
+/// who takes every point shoots the moon.
+pub fn hearts_scores(options: &HeartsOptions, taken: &[Points]) -> Vec<ScoreBreakdown> {
+    let total: Points = taken.iter().sum();
+    let shooter = taken
+        .iter()
+        .position(|points| total > 0 && *points == total);
+    taken
+        .iter()
+        .enumerate()
+        .map(|(p, points)| {
+            let mut items = vec![ScoreItem::Awarded(*points)];
+            match (shooter, options.moon_shot) {
+                (Some(s), MoonShot::AddToOthers) if s == p => {
+                    items.push(ScoreItem::MoonShot(-total))
+                }
+                (Some(_), MoonShot::AddToOthers) => items.push(ScoreItem::MoonShot(total)),
+                (Some(s), MoonShot::SubtractFromShooter) if s == p => {
+                    items.push(ScoreItem::MoonShot(-total * 2))
+                }
+                _ => {}
+            }
+            ScoreBreakdown { items }
+        })
+        .collect()
+}
+
+/// A Spades partnership's score. members holds each partner's bid and the
+/// tricks they took. A nil bidder's tricks don't count toward the contract
+/// but do count as bags. Returns the score and the bags carried forward.
+pub fn spades_score(
+    options: &SpadesOptions,
+    members: &[(TrickBid, usize)],
+    bags_before: u8,
+) -> (ScoreBreakdown, u8) {
+    let mut items = Vec::new();
+    let contract: usize = members.iter().map(|(bid, _)| bid.tricks() as usize).sum();
+    let taken: usize = members
+        .iter()
+        .filter(|(bid, _)| !bid.is_nil())
+        .map(|(_, tricks)| *tricks)
+        .sum();
+
+    let mut bags = 0;
+    if contract > 0 {
+        match taken >= contract {
+            true => {
+                items.push(ScoreItem::Contract(10 * contract as Points));
+                bags += taken - contract;
+            }
+            false => items.push(ScoreItem::Contract(-10 * contract as Points)),
+        }
+    }
+    for (bid, tricks) in members {
+        let bonus = match bid {
+            TrickBid::Nil => options.nil_bonus,
+            TrickBid::BlindNil => options.blind_nil_bonus,
+            TrickBid::Tricks(_) => continue,
+        };
+        match tricks {
+            0 => items.push(ScoreItem::Nil(bonus)),
+            _ => {
+                items.push(ScoreItem::Nil(-bonus));
+                bags += tricks;
+            }
+        }
+    }
+    if bags > 0 {
+        items.push(ScoreItem::Bags(bags as Points));
+    }
+
+    let mut carried = bags_before as usize + bags;
+    while carried >= options.bag_limit as usize {
+        items.push(ScoreItem::BagPenalty(-options.bag_penalty));
+        carried -= options.bag_limit as usize;
+    }
+    (ScoreBreakdown { items }, carried as u8)
+}
+
+/// The Euchre makers' and defenders' scores, from the tricks the makers
+/// took. Only one side scores.
+pub fn euchre_scores(
+    options: &EuchreOptions,
+    makers_tricks: usize,
+    hand_size: usize,
+    alone: bool,
+) -> (ScoreBreakdown, ScoreBreakdown) {
+    let mut makers = ScoreBreakdown::default();
+    let mut defenders = ScoreBreakdown::default();
+    if makers_tricks == hand_size {
+        let march = match alone {
+            true => options.alone_march,
+            false => options.march,
+        };
+        makers.items.push(ScoreItem::March(march));
+    } else if makers_tricks * 2 > hand_size {
+        makers.items.push(ScoreItem::Awarded(1));
+    } else {
+        defenders.items.push(ScoreItem::Euchre(options.euchre));
+    }
+    (makers, defenders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(contract: Points, makers_points: Points, makers_tricks: usize) -> HandSummary {
+        HandSummary {
+            contract,
+            makers: SideTake {
+                points: makers_points,
+                tricks: makers_tricks,
+            },
+            total: SideTake {
+                points: 120,
+                tricks: 9,
+            },
+            alone: false,
+        }
+    }
+
+    fn defenders(hand: &HandSummary) -> SideTake {
+        SideTake {
+            points: hand.total.points - hand.makers.points,
+            tricks: hand.total.tricks - hand.makers.tricks,
+        }
+    }
+
+    #[test]
+    fn made_scores_points_taken() {
+        let options = GameOptions::new();
+        let hand = hand(80, 90, 6);
+        assert_eq!(makers_score(&options, &hand).total(), 90);
+        assert_eq!(
+            defenders_score(&options, &hand, defenders(&hand), 0).total(),
+            30
+        );
+    }
+
+    #[test]
+    fn defenders_use_their_own_options() {
+        let mut options = GameOptions::new();
+        options.defenders_points_awarded_for_win = PointsAwarded::Fixed(50);
+        options.defenders_points_awarded_for_loss = PointsAwarded::Fixed(5);
+        let set = hand(100, 60, 4);
+        assert_eq!(
+            defenders_score(&options, &set, defenders(&set), 0).total(),
+            50
+        );
+        let made = hand(100, 110, 7);
+        assert_eq!(
+            defenders_score(&options, &made, defenders(&made), 0).total(),
+            5
+        );
+    }
+
+    #[test]
+    fn set_subtracts_bid() {
+        let mut options = GameOptions::new();
+        options.makers_points_awarded_for_loss = PointsAwarded::MinusBid;
+        let hand = hand(100, 60, 4);
+        let breakdown = makers_score(&options, &hand);
+        assert_eq!(breakdown.items, vec![ScoreItem::Awarded(-100)]);
+        assert_eq!(breakdown.total(), -100);
+    }
+
+    #[test]
+    fn shoot_the_moon_bonuses() {
+        let mut options = GameOptions::new();
+        options.all_tricks_bonus = 50;
+        options.all_points_bonus = 20;
+        let hand = hand(100, 120, 9);
+        let breakdown = makers_score(&options, &hand);
+        assert_eq!(
+            breakdown.items,
+            vec![
+                ScoreItem::Awarded(120),
+                ScoreItem::AllTricksBonus(50),
+                ScoreItem::AllPointsBonus(20),
+            ]
+        );
+        let defenders = defenders(&hand);
+        assert_eq!(defenders_score(&options, &hand, defenders, 0).total(), 0);
+    }
+
+    #[test]
+    fn all_points_without_all_tricks() {
+        let mut options = GameOptions::new();
+        options.all_tricks_bonus = 50;
+        options.all_points_bonus = 20;
+        let hand = hand(100, 120, 8);
+        assert_eq!(makers_score(&options, &hand).total(), 140);
+    }
+
+    #[test]
+    fn cap_limits_hand_score() {
+        let mut options = GameOptions::new();
+        options.all_tricks_bonus = 50;
+        options.hand_score_cap = Some(150);
+        let hand = hand(100, 120, 9);
+        let breakdown = makers_score(&options, &hand);
+        assert_eq!(breakdown.items.last(), Some(&ScoreItem::Cap(-20)));
+        assert_eq!(breakdown.total(), 150);
+    }
+
+    #[test]
+    fn cap_leaves_negative_scores() {
+        let mut options = GameOptions::new();
+        options.makers_points_awarded_for_loss = PointsAwarded::MinusBid;
+        options.hand_score_cap = Some(50);
+        let hand = hand(100, 60, 4);
+        assert_eq!(makers_score(&options, &hand).total(), -100);
+    }
+
+    #[test]
+    fn alone_multiplies_made_contract() {
+        let mut options = GameOptions::new();
+        options.alone_multiplier = Some(2);
+        let mut made = hand(80, 90, 6);
+        made.alone = true;
+        assert_eq!(
+            makers_score(&options, &made).items,
+            vec![ScoreItem::Awarded(90), ScoreItem::AloneBonus(90)]
+        );
+
+        let mut set = hand(100, 60, 4);
+        set.alone = true;
+        assert_eq!(makers_score(&options, &set).total(), 0);
+    }
+
+    #[test]
+    fn defenders_above_game_score_nothing() {
+        let mut options = GameOptions::new();
+        options.defenders_score_only_below_game = true;
+        let hand = hand(80, 90, 6);
+        let defenders = defenders(&hand);
+
+        let below = defenders_score(&options, &hand, defenders, options.winning_score - 10);
+        assert_eq!(below.total(), 30);
+
+        let above = defenders_score(&options, &hand, defenders, options.winning_score);
+        assert_eq!(
+            above.items,
+            vec![ScoreItem::Awarded(30), ScoreItem::AboveGame(-30)]
+        );
+        assert_eq!(above.total(), 0);
+    }
+
+    #[test]
+    fn hearts_moon_shot() {
+        let mut options = GameOptions::new().hearts;
+        let totals = |options: &HeartsOptions, taken: &[Points]| -> Vec<Points> {
+            hearts_scores(options, taken)
+                .iter()
+                .map(|b| b.total())
+                .collect()
+        };
+        assert_eq!(totals(&options, &[13, 5, 8, 0]), vec![13, 5, 8, 0]);
+        assert_eq!(totals(&options, &[0, 26, 0, 0]), vec![26, 0, 26, 26]);
+        options.moon_shot = MoonShot::SubtractFromShooter;
+        assert_eq!(totals(&options, &[0, 26, 0, 0]), vec![0, -26, 0, 0]);
+    }
+
+    #[test]
+    fn spades_contracts_and_bags() {
+        let options = GameOptions::new().spades;
+        let (made, bags) = spades_score(
+            &options,
+            &[(TrickBid::Tricks(4), 3), (TrickBid::Tricks(2), 5)],
+            0,
+        );
+        assert_eq!(
+            made.items,
+            vec![ScoreItem::Contract(60), ScoreItem::Bags(2)]
+        );
+        assert_eq!(bags, 2);
+
+        let (set, bags) = spades_score(
+            &options,
+            &[(TrickBid::Tricks(4), 3), (TrickBid::Tricks(2), 1)],
+            9,
+        );
+        assert_eq!(set.total(), -60);
+        assert_eq!(bags, 9);
+
+        let (penalty, bags) = spades_score(
+            &options,
+            &[(TrickBid::Tricks(3), 5), (TrickBid::Tricks(1), 0)],
+            9,
+        );
+        assert_eq!(
+            penalty.items,
+            vec![
+                ScoreItem::Contract(40),
+                ScoreItem::Bags(1),
+                ScoreItem::BagPenalty(-100)
+            ]
+        );
+        assert_eq!(bags, 0);
+    }
+
+    #[test]
+    fn spades_nil_bids() {
+        let options = GameOptions::new().spades;
+        let (made, _) = spades_score(&options, &[(TrickBid::Nil, 0), (TrickBid::Tricks(3), 4)], 0);
+        assert_eq!(made.total(), 100 + 30 + 1);
+
+        // The nil bidder's tricks are bags, not part of the contract.
+        let (failed, bags) = spades_score(
+            &options,
+            &[(TrickBid::BlindNil, 2), (TrickBid::Tricks(3), 2)],
+            0,
+        );
+        assert_eq!(
+            failed.items,
+            vec![
+                ScoreItem::Contract(-30),
+                ScoreItem::Nil(-200),
+                ScoreItem::Bags(2)
+            ]
+        );
+        assert_eq!(bags, 2);
+    }
+
+    #[test]
+    fn euchre_points_marches_and_euchres() {
+        let options = GameOptions::new().euchre;
+        let (made, defended) = euchre_scores(&options, 3, 5, false);
+        assert_eq!(made.items, vec![ScoreItem::Awarded(1)]);
+        assert_eq!(defended.total(), 0);
+
+        let (march, _) = euchre_scores(&options, 5, 5, false);
+        assert_eq!(march.items, vec![ScoreItem::March(2)]);
+        let (alone_march, _) = euchre_scores(&options, 5, 5, true);
+        assert_eq!(alone_march.items, vec![ScoreItem::March(4)]);
+
+        let (euchred, euchre) = euchre_scores(&options, 2, 5, true);
+        assert_eq!(euchred.total(), 0);
+        assert_eq!(euchre.items, vec![ScoreItem::Euchre(2)]);
+    }
+}
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+use crate::card::{CardSuit, Points};
+use crate::game_options::PartnerKind;
+use crate::player::PlayerId;
+
+/// One hand on a team's scoresheet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoresheetRow {
+    pub dealer: PlayerId,
+    /// None when the game has no bidding, as in Hearts.
+    pub bidder: Option<PlayerId>,
+    pub bid: Option<Points>,
+    /// None for no trump.
+    pub trump: Option<CardSuit>,
+    /// Taken by the team, nest included.
+    pub points_taken: Points,
+    pub nest_points: Points,
+    /// Whether the makers made their bid. The same on every team's row, and
+    /// false with no maker.
+    pub made: bool,
+    pub score: Points,
+    pub total: Points,
+}
+
+/// Players who score together for the whole game. With no fixed
+/// partnerships, each player is their own team.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Team {
+    pub players: Vec<PlayerId>,
+    pub scoresheet: Vec<ScoresheetRow>,
+    /// Spades: overtricks carried from hand to hand. See SpadesOptions::bag_limit.
+    pub bags: u8,
+}
+
+impl Team {
+    pub fn new(players: Vec<PlayerId>) -> Self {
+        Self {
+            players,
+            scoresheet: Vec::new(),
+            bags: 0,
+        }
+    }
+
+    /// The teams for a partnership mode. Called partnerships only last a
+    /// hand, so they get a team per player like cutthroat.
+    pub fn teams_for(partner_kind: PartnerKind, player_count: PlayerId) -> Vec<Team> {
+        match partner_kind {
+            PartnerKind::Across => (0..player_count / 2)
+                .map(|p| Team::new(vec![p, p + player_count / 2]))
+                .collect(),
+            PartnerKind::Alternate => (0..2)
+                .map(|first| Team::new((first..player_count).step_by(2).collect()))
+                .collect(),
+            PartnerKind::None | PartnerKind::Called => {
+                (0..player_count).map(|p| Team::new(vec![p])).collect()
+            }
+        }
+    }
+
+    pub fn contains(&self, p: PlayerId) -> bool {
+        self.players.contains(&p)
+    }
+
+    pub fn score(&self) -> Points {
+        match self.scoresheet.last() {
+            Some(row) => row.total,
+            None => 0,
+        }
+    }
+
+    /// Names the team by its players, eg "P0 & P2".
+    pub fn name(&self) -> String {
+        let names: Vec<String> = self.players.iter().map(|p| format!("P{p}")).collect();
+        names.join(" & ")
+    }
+}
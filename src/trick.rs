@@ -1,7 +1,10 @@
+use serde::{Deserialize, Serialize};
+
 use crate::card::{Card, CardId, Points};
+use crate::game_options::DuplicateWinner;
 use crate::player::PlayerId;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Trick {
     pub card_ids: Vec<Option<CardId>>,
     pub is_empty: bool,
@@ -9,6 +12,12 @@ pub struct Trick {
     pub winning_card: Option<Card>,
     pub winner: Option<PlayerId>,
     pub points: Points, // could be negative in a game like Hearts
+    /// Counts from 0 within the hand.
+    pub number: u8,
+    /// Seats that play no card to this trick, eg the partner of a lone maker.
+    pub sitting_out: Vec<PlayerId>,
+    /// See GameOptions::duplicate_winner.
+    pub duplicate_winner: DuplicateWinner,
 }
 
 impl Trick {
@@ -24,12 +33,21 @@ impl Trick {
             winning_card: None,
             winner: None,
             points: 0,
+            number: 0,
+            sitting_out: Vec::new(),
+            duplicate_winner: DuplicateWinner::FirstPlayed,
         }
     }
 
+    pub fn short_handed(player_count: PlayerId, sitting_out: &[PlayerId]) -> Self {
+        let mut trick = Trick::new(player_count);
+        trick.sitting_out = sitting_out.to_vec();
+        trick
+    }
+
     pub fn completed(&self) -> bool {
-        for id in &self.card_ids {
-            if id.is_none() {
+        for (p, id) in self.card_ids.iter().enumerate() {
+            if id.is_none() && !self.sitting_out.contains(&p) {
                 return false;
             }
         }
@@ -44,7 +62,7 @@ impl Trick {
         if !self.is_empty {
             if let Some(lead_card) = &self.lead_card {
                 if cards_matching_lead > 0 {
-                    if card.suit != lead_card.suit {
+                    if !card.follows(lead_card) {
                         return false;
                     }
                 }
@@ -53,7 +71,9 @@ impl Trick {
         true
     }
 
-    pub fn add_card(&mut self, p_id: PlayerId, card: &mut Card) {
+    /// takes_lead is whether the card beats the winning card, by the rules
+    /// being played. The first card always leads.
+    pub fn add_card(&mut self, p_id: PlayerId, card: &mut Card, takes_lead: bool) {
         card.face_up = true;
         if self.is_empty {
             self.lead_card = Some(card.clone());
@@ -63,7 +83,7 @@ impl Trick {
             self.winner = Some(p_id);
             self.is_empty = false;
         } else {
-            if self.takes_lead(&card) {
+            if takes_lead {
                 self.winning_card = Some(card.clone());
                 self.winner = Some(p_id);
             }
@@ -75,6 +95,14 @@ impl Trick {
 
     pub fn takes_lead(&self, card: &Card) -> bool {
         if let Some(winning_card) = &self.winning_card {
+            // A copy of the winning card, ranking the same.
+            if card == winning_card && card.game_rank == winning_card.game_rank {
+                return self.duplicate_winner == DuplicateWinner::LastPlayed;
+            }
+            // All trump ranks against each other, special cards included.
+            if card.is_trump && winning_card.is_trump {
+                return card.game_rank > winning_card.game_rank;
+            }
             if card.suit == winning_card.suit {
                 return card.game_rank > winning_card.game_rank;
             } else {
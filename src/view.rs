@@ -2,20 +2,21 @@ use std::{collections::VecDeque, sync::mpsc::Sender};
 
 use notan::{
     app::{assets::Assets, App, Color, Graphics},
-    draw::{CreateFont, Font},
+    draw::{CreateFont, DrawTextSection, Font},
     math::{vec2, Affine2, Vec2},
     Event,
 };
 use slotmap::SlotMap;
 
 use crate::{
+    bid::TrickBid,
     bid_selector::BidSelector,
-    card::{Card, CardId, CardSuit, SelectState},
+    card::{Card, CardId, CardSuit, Points, SelectState},
     card_update::{CardGroup, CardUpdate},
     card_view::CardView,
     game::{Game, PlayerAction},
     image::Image,
-    image_button::ImageButton,
+    image_button::{ButtonState, ImageButton},
     player::PlayerId,
     view_geom::{ViewGeom, BUTTON_POS, VIEW_CENTER},
     view_trait::ViewTrait,
@@ -37,9 +38,23 @@ pub struct View {
     dealer_marker: Image,
     pub deal_button: ImageButton<PlayerAction>,
     pub bid_selector: BidSelector,
+    /// Each player's latest bid, shown by their seat.
+    bid_texts: Vec<String>,
+    /// The called card, then the revealed partner.
+    call_text: String,
+    /// Running totals, shown in the corner.
+    score_texts: Vec<String>,
+    /// Shown in the center, eg at the end of the game.
+    message: String,
     discard_panel: Image,
     discard_outlines: Vec<Image>,
+    /// Ends the nest exchange. Enabled once the nest is full.
+    done_button: ImageButton<PlayerAction>,
+    /// One per opponent seat. Lets the human call a renege during play.
+    renege_buttons: Vec<ImageButton<PlayerAction>>,
     trump_marker: Image,
+    /// Shown in place of the trump marker for a no trump hand.
+    no_trump: bool,
     play_outline: Image,
 
     fps_update: f32,
@@ -69,6 +84,8 @@ impl View {
         let bid_selector = View::create_bid_selector(gfx, sender.clone());
         let discard_panel = View::create_discard_panel();
         let discard_outlines = View::create_discard_outlines(game);
+        let done_button = View::create_done_button(sender.clone());
+        let renege_buttons = View::create_renege_buttons(game, sender.clone());
         let trump_marker = View::create_trump_marker();
         let play_outline = View::create_play_outline();
 
@@ -82,9 +99,16 @@ impl View {
             dealer_marker,
             deal_button,
             bid_selector,
+            bid_texts: vec![String::new(); game.player_count],
+            call_text: String::new(),
+            score_texts: Vec::new(),
+            message: String::new(),
             discard_panel,
             discard_outlines,
+            done_button,
+            renege_buttons,
             trump_marker,
+            no_trump: false,
             play_outline,
             fps_update: 0.0,
         }
@@ -103,13 +127,16 @@ impl View {
             "diamond",
             "diamond_mouse_over",
             "discard",
+            "done_disabled",
             "done_enabled",
+            "done_mouse_over",
             "heart",
             "heart_mouse_over",
             "pass_enabled",
             "pass_mouse_over",
             "spade",
             "spade_mouse_over",
+            "square",
         ];
         let tex_names: Vec<String> = names.iter().map(|&n| n.to_string()).collect();
         crate::TEX_LOADER
@@ -136,7 +163,10 @@ impl View {
                 Some(sender.clone()),
             );
             card_views.push(card_view);
-            tex_names.push(card.file_string());
+            // Copies share a texture.
+            if !tex_names.contains(&card.file_string()) {
+                tex_names.push(card.file_string());
+            }
         }
 
         crate::TEX_LOADER
@@ -191,13 +221,45 @@ impl View {
         image
     }
 
+    fn create_done_button(sender: Sender<PlayerAction>) -> ImageButton<PlayerAction> {
+        let mut trans = VIEW_CENTER;
+        trans.y += 200.0;
+        let mut button = ImageButton::new(
+            trans,
+            "done_enabled",
+            "done_mouse_over",
+            "done_disabled",
+            0.5,
+            "",
+            Some(sender),
+        );
+        button.mouse_up_message = Some(PlayerAction::EndNestExchange);
+        button.visible = false;
+        button
+    }
+
+    fn create_renege_buttons(game: &Game, sender: Sender<PlayerAction>) -> Vec<ImageButton<PlayerAction>> {
+        let human = (0..game.player_count).find(|p| !game.player_is_bot(*p)).unwrap_or(0);
+        let mut buttons = Vec::new();
+        for p in (0..game.player_count).filter(|p| *p != human) {
+            let trans = ViewGeom::bid_view_position(p, game.player_count) + vec2(0.0, 30.0);
+            let mut button =
+                ImageButton::new(trans, "square", "square", "", 0.4, "Renege?", Some(sender.clone()));
+            button.font_size = 10.0;
+            button.mouse_up_message = Some(PlayerAction::CallRenege(human, p));
+            button.visible = false;
+            buttons.push(button);
+        }
+        buttons
+    }
+
     fn create_discard_outlines(game: &Game) -> Vec<Image> {
         let mut outlines = Vec::new();
-        for idx in 0..2 {
+        for idx in 0..game.exchange_size() {
             let mut image = Image::new("cards/outline", Vec2::ZERO, 0.35);
             let update = CardUpdate {
                 group: CardGroup::NestExchange,
-                group_len: game.options.nest_size as usize,
+                group_len: game.exchange_size(),
                 group_index: idx,
                 ..Default::default()
             };
@@ -241,6 +303,25 @@ impl View {
         }
     }
 
+    /// Apply all the updates at once, without animating. For laying out a
+    /// loaded game.
+    pub fn place_cards(&mut self, updates: &mut VecDeque<CardUpdate>) {
+        while let Some(update) = updates.pop_front() {
+            let card_view = self
+                .card_views
+                .iter_mut()
+                .find(|s| s.id == update.id)
+                .unwrap();
+
+            card_view.place_at(update);
+            card_view.face_up = update.face_up;
+            card_view.select_state = update.select_state;
+            card_view.update_pt_text(update.points);
+            card_view.update = update;
+        }
+        self.card_views_z_order_dirty = true;
+    }
+
     /// Returns false if the card did not need updating.
     fn update_card(&mut self, update: CardUpdate) -> bool {
         let card_view = self
@@ -259,6 +340,9 @@ impl View {
 
         card_view.face_up = update.face_up;
         card_view.select_state = update.select_state;
+        if card_view.update.points != update.points {
+            card_view.update_pt_text(update.points);
+        }
         card_view.update = update;
         true
     }
@@ -283,11 +367,103 @@ impl View {
         if game.active_player_is_bot() {
             println!("bot bidding: {}", game.active_player);
         } else {
-            self.bid_selector.visible = true;
+            self.bid_selector
+                .show_bids(game.auction.legal_bids(&game.options));
             println!("bid_selector visible");
         }
     }
 
+    pub fn get_trick_bid(&mut self, game: &Game) {
+        if game.active_player_is_bot() {
+            println!("bot bidding tricks: {}", game.active_player);
+        } else if game.legal_trick_bids().contains(&TrickBid::BlindNil) {
+            self.bid_selector.show_blind_nil();
+        } else {
+            self.bid_selector.show_trick_bids(&game.legal_trick_bids());
+        }
+    }
+
+    pub fn get_trump_bid(&mut self, game: &Game) {
+        if game.active_player_is_bot() {
+            println!("bot bidding trump: {}", game.active_player);
+        } else {
+            self.bid_selector.show_trump_bids(&game.legal_trump_bids());
+        }
+    }
+
+    pub fn set_trump_bid(&mut self, p: PlayerId, suit: Option<CardSuit>) {
+        self.bid_texts[p] = match suit {
+            Some(suit) => suit.to_string(),
+            None => "Pass".to_string(),
+        };
+    }
+
+    pub fn set_trick_bid(&mut self, p: PlayerId, bid: TrickBid) {
+        self.bid_texts[p] = bid.name();
+    }
+
+    pub fn set_bid(&mut self, p: PlayerId, points: Option<Points>) {
+        self.bid_texts[p] = match points {
+            Some(points) => points.to_string(),
+            None => "Pass".to_string(),
+        };
+    }
+
+    pub fn clear_bids(&mut self) {
+        for text in &mut self.bid_texts {
+            text.clear();
+        }
+    }
+
+    pub fn get_call_partner(&mut self, game: &Game) {
+        if game.active_player_is_bot() {
+            println!("bot calling partner: {}", game.active_player);
+        } else {
+            let cards = game
+                .callable_card_ids()
+                .iter()
+                .map(|id| (game.cards[*id].name(), *id))
+                .collect();
+            self.bid_selector.show_call(cards);
+        }
+    }
+
+    pub fn get_go_alone(&mut self, game: &Game) {
+        if game.active_player_is_bot() {
+            println!("bot deciding to go alone: {}", game.active_player);
+        } else {
+            self.bid_selector.show_alone();
+        }
+    }
+
+    pub fn update_call_text(&mut self, game: &Game) {
+        if game.alone {
+            self.call_text = "Maker alone".to_string();
+            return;
+        }
+        self.call_text = match game.called_card {
+            None => String::new(),
+            Some(id) => match (game.partner_revealed, game.maker) {
+                (true, Some(maker)) => match game.players[maker].partners.first() {
+                    Some(p) => format!("Partner: P{p}"),
+                    None => "Maker alone".to_string(),
+                },
+                _ => format!("Called: {}", game.cards[id].name()),
+            },
+        };
+    }
+
+    pub fn get_trump(&mut self, game: &Game) {
+        if game.active_player_is_bot() {
+            println!("bot choosing trump: {}", game.active_player);
+        } else {
+            self.bid_selector.show_trump(
+                &game.trump_choices(),
+                game.options.trump_rules.allow_no_trump,
+            );
+        }
+    }
+
     pub fn set_trump(&mut self, suit: Option<CardSuit>) {
         match suit {
             Some(suit) => {
@@ -295,18 +471,77 @@ impl View {
                 self.trump_marker.visible = true;
             }
             None => {
-                // set some kind of no-trump texture
+                self.trump_marker.visible = false;
+                self.no_trump = true;
             }
         }
     }
 
+    pub fn clear_trump(&mut self) {
+        self.trump_marker.visible = false;
+        self.no_trump = false;
+    }
+
+    /// Team totals from the scoresheets, with the last hand's score and any
+    /// nest points it included.
+    pub fn update_scores(&mut self, game: &Game) {
+        self.score_texts = game
+            .teams
+            .iter()
+            .map(|team| match team.scoresheet.last() {
+                Some(row) if row.nest_points != 0 => format!(
+                    "{}: {} ({:+}, nest {:+})",
+                    team.name(),
+                    row.total,
+                    row.score,
+                    row.nest_points
+                ),
+                Some(row) => format!("{}: {} ({:+})", team.name(), row.total, row.score),
+                None => format!("{}: 0", team.name()),
+            })
+            .collect();
+    }
+
+    pub fn show_game_over(&mut self, game: &Game) {
+        // Teams by index, so partners who win together are named once.
+        let mut teams: Vec<usize> = game
+            .winners
+            .iter()
+            .map(|p| game.teams.iter().position(|team| team.contains(*p)).unwrap())
+            .collect();
+        teams.sort();
+        teams.dedup();
+        let winners: Vec<String> = teams.iter().map(|t| game.teams[*t].name()).collect();
+        self.show_message(&format!("Game over. Winner: {}", winners.join(", ")));
+    }
+
+    /// The renege buttons show during trick play when reneging is allowed.
+    pub fn show_renege_buttons(&mut self, visible: bool) {
+        for button in &mut self.renege_buttons {
+            button.visible = visible;
+        }
+    }
+
+    pub fn show_message(&mut self, text: &str) {
+        self.message = text.to_string();
+    }
+
+    pub fn clear_message(&mut self) {
+        self.message.clear();
+    }
+
     pub fn get_discard(&mut self, game: &Game) {
         if game.active_player_is_bot() {
             println!("bot choosing discard: {}", game.active_player);
         } else {
             self.discard_panel.visible = true;
+            self.done_button.visible = true;
+            self.done_button.state = match game.nest_exchange_ready() {
+                true => ButtonState::Enabled,
+                false => ButtonState::Disabled,
+            };
 
-            // Set message for eligible cards.
+            // Set message for eligible cards. Cards in the nest can be taken back.
             for id in game.active_hand() {
                 if let Some(card) = game.cards.get(*id) {
                     let card_view = self.card_views.iter_mut().find(|s| s.id == *id).unwrap();
@@ -316,6 +551,15 @@ impl View {
                     }
                 }
             }
+            for id in &game.nest {
+                if let Some(card) = game.cards.get(*id) {
+                    let card_view = self.card_views.iter_mut().find(|s| s.id == *id).unwrap();
+                    card_view.mouse_up_message = match card.select_state {
+                        SelectState::Selectable => Some(PlayerAction::TakeCardFromNest(*id)),
+                        _ => None,
+                    }
+                }
+            }
         }
 
         for outline in &mut self.discard_outlines {
@@ -325,6 +569,7 @@ impl View {
 
     pub fn end_discard(&mut self) {
         self.discard_panel.visible = false;
+        self.done_button.visible = false;
         for outline in &mut self.discard_outlines {
             outline.visible = false;
         }
@@ -392,6 +637,19 @@ impl ViewTrait for View {
             send_msg = false;
         }
 
+        if self
+            .done_button
+            .handle_mouse_event(event, screen_pt, parent_affine, send_msg)
+        {
+            send_msg = false;
+        }
+
+        for button in &mut self.renege_buttons {
+            if button.handle_mouse_event(event, screen_pt, parent_affine, send_msg) {
+                send_msg = false;
+            }
+        }
+
         // Iterate in reverse to check on-top sprites first.
         for card_view in self.card_views.iter_mut().rev() {
             if card_view.handle_mouse_event(event, screen_pt, parent_affine, send_msg) {
@@ -434,16 +692,72 @@ impl ViewTrait for View {
         }
         self.play_outline.draw(draw, parent_affine);
 
+        let font = crate::FONT.lock().unwrap().expect("Font is None");
+        let player_count = self.bid_texts.len();
+        for (p, text) in self.bid_texts.iter().enumerate() {
+            if text.is_empty() {
+                continue;
+            }
+            let pos = ViewGeom::bid_view_position(p, player_count);
+            draw.text(&font, text)
+                .position(pos.x, pos.y)
+                .size(16.0 * 2.0)
+                .h_align_center()
+                .v_align_middle()
+                .color(Color::WHITE);
+        }
+
+        if self.no_trump {
+            draw.text(&font, "No Trump")
+                .position(VIEW_CENTER.x, VIEW_CENTER.y)
+                .size(16.0 * 2.0)
+                .h_align_center()
+                .v_align_middle()
+                .color(Color::WHITE);
+        }
+
+        if !self.call_text.is_empty() {
+            let pos = VIEW_CENTER + vec2(0.0, 40.0);
+            draw.text(&font, &self.call_text)
+                .position(pos.x, pos.y)
+                .size(14.0 * 2.0)
+                .h_align_center()
+                .v_align_middle()
+                .color(Color::WHITE);
+        }
+
+        for (idx, text) in self.score_texts.iter().enumerate() {
+            draw.text(&font, text)
+                .position(10.0, 20.0 + idx as f32 * 20.0)
+                .size(14.0 * 2.0)
+                .h_align_left()
+                .v_align_middle()
+                .color(Color::WHITE);
+        }
+
         for card_view in &mut self.card_views {
             card_view.draw(draw, parent_affine);
         }
 
+        if !self.message.is_empty() {
+            draw.text(&font, &self.message)
+                .position(VIEW_CENTER.x, VIEW_CENTER.y)
+                .size(24.0 * 2.0)
+                .h_align_center()
+                .v_align_middle()
+                .color(Color::WHITE);
+        }
+
         // Buttons and panels
         self.deal_button.draw(draw, parent_affine);
 
         self.bid_selector.draw(draw, parent_affine);
 
         self.discard_panel.draw(draw, parent_affine);
+        self.done_button.draw(draw, parent_affine);
+        for button in &mut self.renege_buttons {
+            button.draw(draw, parent_affine);
+        }
 
         // FPS
         if self.fps_update < 0.0 {